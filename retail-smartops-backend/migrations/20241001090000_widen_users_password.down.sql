@@ -0,0 +1,9 @@
+/*
+====================================================================================================================
+=================== Migration script for reverting users.password to its original width ===========================
+====================================================================================================================
+*/
+
+/* Alter Users Table */
+ALTER TABLE users
+    ALTER COLUMN password TYPE VARCHAR(50);
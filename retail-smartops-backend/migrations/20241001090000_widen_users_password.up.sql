@@ -0,0 +1,9 @@
+/*
+====================================================================================================================
+=================== Migration script for widening users.password to hold argon2 PHC hash strings ==================
+====================================================================================================================
+ */
+
+/* Alter Users Table */
+ALTER TABLE users
+    ALTER COLUMN password TYPE VARCHAR(255);
@@ -0,0 +1,16 @@
+/*
+====================================================================================================================
+=========================== Migration script for dropping sessions schema ==========================================
+====================================================================================================================
+*/
+
+/* Drop Sessions Table */
+DROP TABLE IF EXISTS sessions;
+
+/* Revert Users Table timestamps */
+ALTER TABLE users
+    ALTER COLUMN created_at DROP NOT NULL,
+    ALTER COLUMN created_at TYPE TIMESTAMP USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at DROP NOT NULL,
+    ALTER COLUMN updated_at TYPE TIMESTAMP USING updated_at AT TIME ZONE 'UTC',
+    ALTER COLUMN is_active DROP NOT NULL;
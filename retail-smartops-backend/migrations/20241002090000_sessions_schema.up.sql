@@ -0,0 +1,27 @@
+/*
+====================================================================================================================
+=========================== Migration script for creating sessions schema ==========================================
+====================================================================================================================
+ */
+
+/* Align Users Table timestamps with the User entity */
+ALTER TABLE users
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at SET NOT NULL,
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at SET NOT NULL,
+    ALTER COLUMN is_active SET NOT NULL;
+
+/* Create Sessions Table */
+CREATE TABLE sessions
+(
+    id                 UUID        DEFAULT uuid_generate_v4(),
+    user_id            UUID        NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    refresh_token_hash VARCHAR(64) UNIQUE NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at         TIMESTAMPTZ NOT NULL,
+    revoked_at         TIMESTAMPTZ,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX idx_sessions_user_id ON sessions (user_id);
@@ -0,0 +1,26 @@
+/*
+====================================================================================================================
+=================== Migration script for removing the admin role seed ==============================================
+====================================================================================================================
+*/
+
+/* Drop Admin Permissions */
+DELETE
+FROM permissions
+WHERE id IN (SELECT role_permissions.permission_id
+             FROM role_permissions
+                      JOIN roles ON roles.id = role_permissions.role_id
+             WHERE roles.name = 'admin'
+               AND permissions.entity_name IN ('permissions', 'roles'));
+
+/* Drop Admin Role */
+DELETE
+FROM roles
+WHERE name = 'admin';
+
+/* Alter Permissions Table */
+ALTER TABLE permissions
+    ALTER COLUMN can_read DROP NOT NULL,
+    ALTER COLUMN can_write DROP NOT NULL,
+    ALTER COLUMN can_delete DROP NOT NULL,
+    ALTER COLUMN can_update DROP NOT NULL;
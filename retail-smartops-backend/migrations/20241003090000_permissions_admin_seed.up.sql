@@ -0,0 +1,30 @@
+/*
+====================================================================================================================
+=================== Migration script for tightening permissions and seeding the admin role =========================
+====================================================================================================================
+ */
+
+/* Alter Permissions Table */
+ALTER TABLE permissions
+    ALTER COLUMN can_read SET NOT NULL,
+    ALTER COLUMN can_write SET NOT NULL,
+    ALTER COLUMN can_delete SET NOT NULL,
+    ALTER COLUMN can_update SET NOT NULL;
+
+/* Seed Admin Role */
+INSERT INTO roles (name)
+VALUES ('admin')
+ON CONFLICT (name) DO NOTHING;
+
+/* Seed Admin Permissions */
+WITH admin_permissions AS (
+    INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+        VALUES ('permissions', TRUE, TRUE, TRUE, TRUE),
+               ('roles', TRUE, TRUE, TRUE, TRUE)
+        RETURNING id)
+INSERT
+INTO role_permissions (role_id, permission_id)
+SELECT roles.id, admin_permissions.id
+FROM roles,
+     admin_permissions
+WHERE roles.name = 'admin';
@@ -0,0 +1,20 @@
+/*
+====================================================================================================================
+=========================== Migration script for removing store scope from user_roles ==============================
+====================================================================================================================
+*/
+
+/* Drop store-scoped assignments, they have no global equivalent */
+DELETE
+FROM user_roles
+WHERE store_id IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_user_roles_store_id;
+DROP INDEX IF EXISTS user_roles_assignment_key;
+
+/* Alter User_Role Table */
+ALTER TABLE user_roles
+    DROP COLUMN store_id,
+    ALTER COLUMN assigned_at DROP NOT NULL,
+    ALTER COLUMN assigned_at TYPE TIMESTAMP USING assigned_at AT TIME ZONE 'UTC',
+    ADD PRIMARY KEY (user_id, role_id);
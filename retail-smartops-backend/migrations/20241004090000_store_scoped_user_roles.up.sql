@@ -0,0 +1,20 @@
+/*
+====================================================================================================================
+=========================== Migration script for scoping user_roles to stores ======================================
+====================================================================================================================
+ */
+
+/* Alter User_Role Table */
+ALTER TABLE user_roles
+    DROP CONSTRAINT user_roles_pkey,
+    ALTER COLUMN role_id DROP DEFAULT,
+    ALTER COLUMN assigned_at TYPE TIMESTAMPTZ USING assigned_at AT TIME ZONE 'UTC',
+    ALTER COLUMN assigned_at SET NOT NULL,
+    ADD COLUMN store_id INT REFERENCES stores (store_id) ON DELETE CASCADE;
+
+DROP SEQUENCE IF EXISTS user_roles_role_id_seq;
+
+/* A NULL store_id is a global assignment, so uniqueness treats it as its own scope */
+CREATE UNIQUE INDEX user_roles_assignment_key ON user_roles (user_id, role_id, COALESCE(store_id, 0));
+
+CREATE INDEX idx_user_roles_store_id ON user_roles (store_id);
@@ -0,0 +1,14 @@
+/*
+====================================================================================================================
+=========================== Migration script for revoking the admin role access to users ===========================
+====================================================================================================================
+*/
+
+/* Drop Admin Permissions */
+DELETE
+FROM permissions
+WHERE id IN (SELECT role_permissions.permission_id
+             FROM role_permissions
+                      JOIN roles ON roles.id = role_permissions.role_id
+             WHERE roles.name = 'admin'
+               AND permissions.entity_name = 'users');
@@ -0,0 +1,17 @@
+/*
+====================================================================================================================
+=========================== Migration script for granting the admin role access to users ===========================
+====================================================================================================================
+ */
+
+/* Seed Admin Permissions */
+WITH admin_permissions AS (
+    INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+        VALUES ('users', TRUE, TRUE, TRUE, TRUE)
+        RETURNING id)
+INSERT
+INTO role_permissions (role_id, permission_id)
+SELECT roles.id, admin_permissions.id
+FROM roles,
+     admin_permissions
+WHERE roles.name = 'admin';
@@ -0,0 +1,24 @@
+/*
+====================================================================================================================
+=========================== Migration script for reverting the stores alignment ====================================
+====================================================================================================================
+*/
+
+/* Drop Admin Permissions */
+DELETE
+FROM permissions
+WHERE id IN (SELECT role_permissions.permission_id
+             FROM role_permissions
+                      JOIN roles ON roles.id = role_permissions.role_id
+             WHERE roles.name = 'admin'
+               AND permissions.entity_name = 'stores');
+
+DROP INDEX IF EXISTS idx_stores_owner_id;
+
+/* Alter Stores Table */
+ALTER TABLE stores
+    ALTER COLUMN owner_id DROP NOT NULL,
+    ALTER COLUMN created_at DROP NOT NULL,
+    ALTER COLUMN created_at TYPE TIMESTAMP USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at DROP NOT NULL,
+    ALTER COLUMN updated_at TYPE TIMESTAMP USING updated_at AT TIME ZONE 'UTC';
@@ -0,0 +1,27 @@
+/*
+====================================================================================================================
+=========================== Migration script for aligning stores with the Store entity =============================
+====================================================================================================================
+ */
+
+/* Alter Stores Table */
+ALTER TABLE stores
+    ALTER COLUMN owner_id SET NOT NULL,
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at SET NOT NULL,
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at SET NOT NULL;
+
+CREATE INDEX idx_stores_owner_id ON stores (owner_id);
+
+/* Seed Admin Permissions */
+WITH admin_permissions AS (
+    INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+        VALUES ('stores', TRUE, TRUE, TRUE, TRUE)
+        RETURNING id)
+INSERT
+INTO role_permissions (role_id, permission_id)
+SELECT roles.id, admin_permissions.id
+FROM roles,
+     admin_permissions
+WHERE roles.name = 'admin';
@@ -0,0 +1,18 @@
+/*
+====================================================================================================================
+=========================== Migration script for removing store membership periods ================================
+====================================================================================================================
+*/
+
+DROP INDEX IF EXISTS idx_store_users_user_id;
+
+/* Alter Store_Users Table */
+ALTER TABLE store_users
+    DROP CONSTRAINT store_users_period_check,
+    DROP COLUMN created_at,
+    DROP COLUMN ends_on,
+    DROP COLUMN starts_on,
+    DROP CONSTRAINT store_users_user_id_fkey,
+    DROP CONSTRAINT store_users_store_id_fkey,
+    ADD CONSTRAINT store_users_store_id_fkey FOREIGN KEY (store_id) REFERENCES stores (store_id),
+    ADD CONSTRAINT store_users_user_id_fkey FOREIGN KEY (user_id) REFERENCES users (id);
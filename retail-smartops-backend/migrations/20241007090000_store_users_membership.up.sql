@@ -0,0 +1,21 @@
+/*
+====================================================================================================================
+=========================== Migration script for store membership periods ==========================================
+====================================================================================================================
+ */
+
+/* Alter Store_Users Table */
+ALTER TABLE store_users
+    ALTER COLUMN store_id DROP DEFAULT,
+    DROP CONSTRAINT store_users_store_id_fkey,
+    DROP CONSTRAINT store_users_user_id_fkey,
+    ADD CONSTRAINT store_users_store_id_fkey FOREIGN KEY (store_id) REFERENCES stores (store_id) ON DELETE CASCADE,
+    ADD CONSTRAINT store_users_user_id_fkey FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    ADD COLUMN starts_on  DATE,
+    ADD COLUMN ends_on    DATE,
+    ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ADD CONSTRAINT store_users_period_check CHECK (starts_on IS NULL OR ends_on IS NULL OR starts_on <= ends_on);
+
+DROP SEQUENCE IF EXISTS store_users_store_id_seq;
+
+CREATE INDEX idx_store_users_user_id ON store_users (user_id);
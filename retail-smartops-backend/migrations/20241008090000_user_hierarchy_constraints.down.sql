@@ -0,0 +1,17 @@
+/*
+====================================================================================================================
+=========================== Migration script for removing the user hierarchy constraints ===========================
+====================================================================================================================
+*/
+
+DROP INDEX IF EXISTS idx_user_hierarchy_reports_to;
+
+/* Alter User_Hierarchy Table */
+ALTER TABLE user_hierarchy
+    DROP CONSTRAINT user_hierarchy_no_self_reference,
+    DROP CONSTRAINT user_hierarchy_reports_to_fkey,
+    DROP CONSTRAINT user_hierarchy_user_id_fkey,
+    DROP CONSTRAINT user_hierarchy_pkey,
+    ADD PRIMARY KEY (user_id, reports_to),
+    ADD CONSTRAINT user_hierarchy_user_id_fkey FOREIGN KEY (user_id) REFERENCES users (id),
+    ADD CONSTRAINT user_hierarchy_reports_to_fkey FOREIGN KEY (reports_to) REFERENCES users (id);
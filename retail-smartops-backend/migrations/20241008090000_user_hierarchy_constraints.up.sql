@@ -0,0 +1,40 @@
+/*
+====================================================================================================================
+=========================== Migration script for constraining the user hierarchy ===================================
+====================================================================================================================
+ */
+
+/*
+ Refuse to run while the hierarchy holds rows the new constraints reject. Which manager to keep is a business
+ decision, so offending rows have to be cleaned up by hand before migrating.
+ */
+DO
+$$
+    DECLARE
+        self_references INTEGER;
+        extra_managers  INTEGER;
+    BEGIN
+        SELECT COUNT(*) INTO self_references FROM user_hierarchy WHERE user_id = reports_to;
+
+        SELECT COUNT(*) - COUNT(DISTINCT user_id) INTO extra_managers FROM user_hierarchy;
+
+        IF self_references > 0 OR extra_managers > 0 THEN
+            RAISE EXCEPTION 'user_hierarchy holds % self reference(s) and % extra manager(s)',
+                self_references, extra_managers
+                USING HINT = 'Delete rows where user_id = reports_to and keep a single row per user_id, then retry.';
+        END IF;
+    END
+$$;
+
+/* Alter User_Hierarchy Table */
+ALTER TABLE user_hierarchy
+    DROP CONSTRAINT user_hierarchy_pkey,
+    DROP CONSTRAINT user_hierarchy_user_id_fkey,
+    DROP CONSTRAINT user_hierarchy_reports_to_fkey,
+    ALTER COLUMN reports_to SET NOT NULL,
+    ADD PRIMARY KEY (user_id),
+    ADD CONSTRAINT user_hierarchy_user_id_fkey FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    ADD CONSTRAINT user_hierarchy_reports_to_fkey FOREIGN KEY (reports_to) REFERENCES users (id) ON DELETE CASCADE,
+    ADD CONSTRAINT user_hierarchy_no_self_reference CHECK (user_id <> reports_to);
+
+CREATE INDEX idx_user_hierarchy_reports_to ON user_hierarchy (reports_to);
@@ -0,0 +1,20 @@
+/*
+====================================================================================================================
+=========================== Migration script for removing soft deletion of users ===================================
+====================================================================================================================
+*/
+
+/* Drop Admin Permissions */
+DELETE
+FROM permissions
+WHERE id IN (SELECT role_permissions.permission_id
+             FROM role_permissions
+                      JOIN roles ON roles.id = role_permissions.role_id
+             WHERE roles.name = 'admin'
+               AND permissions.entity_name = 'user_purge');
+
+DROP INDEX IF EXISTS idx_users_deleted_at;
+
+/* Alter Users Table, soft deleted users become visible again */
+ALTER TABLE users
+    DROP COLUMN deleted_at;
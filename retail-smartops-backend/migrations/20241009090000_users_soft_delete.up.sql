@@ -0,0 +1,23 @@
+/*
+====================================================================================================================
+=========================== Migration script for soft deleting users ===============================================
+====================================================================================================================
+ */
+
+/* Alter Users Table */
+ALTER TABLE users
+    ADD COLUMN deleted_at TIMESTAMPTZ;
+
+CREATE INDEX idx_users_deleted_at ON users (deleted_at) WHERE deleted_at IS NOT NULL;
+
+/* Seed Admin Permissions, purging users is reserved to admins */
+WITH admin_permissions AS (
+    INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+        VALUES ('user_purge', FALSE, FALSE, TRUE, FALSE)
+        RETURNING id)
+INSERT
+INTO role_permissions (role_id, permission_id)
+SELECT roles.id, admin_permissions.id
+FROM roles,
+     admin_permissions
+WHERE roles.name = 'admin';
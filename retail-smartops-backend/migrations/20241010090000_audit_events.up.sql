@@ -0,0 +1,37 @@
+/*
+====================================================================================================================
+=========================== Migration script for creating the audit log ============================================
+====================================================================================================================
+ */
+
+/* Create Audit Events Table, the actor has no foreign key so events outlive purged users */
+CREATE TABLE audit_events
+(
+    id          BIGSERIAL,
+    occurred_at TIMESTAMPTZ  NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    actor_id    UUID,
+    action      VARCHAR(30)  NOT NULL,
+    entity      VARCHAR(30)  NOT NULL,
+    entity_id   VARCHAR(64)  NOT NULL,
+    before      JSONB,
+    after       JSONB,
+    ip_address  VARCHAR(45),
+    request_id  VARCHAR(128),
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX idx_audit_events_occurred_at ON audit_events (occurred_at);
+CREATE INDEX idx_audit_events_entity ON audit_events (entity, entity_id);
+CREATE INDEX idx_audit_events_actor_id ON audit_events (actor_id);
+
+/* Seed Admin Permissions, the audit log is read-only */
+WITH admin_permissions AS (
+    INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+        VALUES ('audit', TRUE, FALSE, FALSE, FALSE)
+        RETURNING id)
+INSERT
+INTO role_permissions (role_id, permission_id)
+SELECT roles.id, admin_permissions.id
+FROM roles,
+     admin_permissions
+WHERE roles.name = 'admin';
@@ -0,0 +1,17 @@
+/*
+====================================================================================================================
+=========================== Migration script for dropping the login throttles ======================================
+====================================================================================================================
+*/
+
+/* Drop Admin Permissions */
+DELETE
+FROM permissions
+WHERE id IN (SELECT role_permissions.permission_id
+             FROM role_permissions
+                      JOIN roles ON roles.id = role_permissions.role_id
+             WHERE roles.name = 'admin'
+               AND permissions.entity_name = 'login_lockouts');
+
+/* Drop Login Throttles Table */
+DROP TABLE IF EXISTS login_throttles;
@@ -0,0 +1,28 @@
+/*
+====================================================================================================================
+=========================== Migration script for creating the login throttles ======================================
+====================================================================================================================
+ */
+
+/* Create Login Throttles Table, failed logins are counted per username and per IP address */
+CREATE TABLE login_throttles
+(
+    scope           VARCHAR(10) NOT NULL,
+    key             VARCHAR(64) NOT NULL,
+    failed_attempts INTEGER     NOT NULL DEFAULT 0,
+    last_failed_at  TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    locked_until    TIMESTAMPTZ,
+    PRIMARY KEY (scope, key)
+);
+
+/* Seed Admin Permissions, lockouts can only be lifted */
+WITH admin_permissions AS (
+    INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+        VALUES ('login_lockouts', FALSE, FALSE, TRUE, FALSE)
+        RETURNING id)
+INSERT
+INTO role_permissions (role_id, permission_id)
+SELECT roles.id, admin_permissions.id
+FROM roles,
+     admin_permissions
+WHERE roles.name = 'admin';
@@ -0,0 +1,17 @@
+/*
+====================================================================================================================
+=========================== Migration script for granting the admin role access to role assignments ================
+====================================================================================================================
+ */
+
+/* Seed Admin Permissions, assigning roles is kept apart from editing users so it cannot be used to escalate */
+WITH admin_permissions AS (
+    INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+        VALUES ('user_roles', TRUE, TRUE, TRUE, TRUE)
+        RETURNING id)
+INSERT
+INTO role_permissions (role_id, permission_id)
+SELECT roles.id, admin_permissions.id
+FROM roles,
+     admin_permissions
+WHERE roles.name = 'admin';
@@ -0,0 +1,138 @@
+use crate::auth::token::decode_access_token;
+use crate::entities::user::User;
+use crate::errors::AppError;
+use crate::request_id::current_request_id;
+use crate::AppState;
+use axum::async_trait;
+use axum::extract::{ConnectInfo, FromRequestParts, Request};
+use axum::http::header::AUTHORIZATION;
+use axum::http::request::Parts;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use uuid::Uuid;
+
+/// The authenticated caller of a request.
+///
+/// Extracting `AuthUser` validates the bearer access token from the `Authorization` header,
+/// loads the user it was issued to and rejects the request with `AppError::Unauthorized`
+/// if the token is missing, invalid or belongs to an inactive user.
+///
+/// # Fields
+///
+/// * `user` - The user entity of the caller.
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub user: User,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        app_state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        // Reuse the identity resolved by `require_auth` when the route is behind it.
+        if let Some(auth_user) = parts.extensions.get::<AuthUser>() {
+            return Ok(auth_user.clone());
+        }
+
+        let access_token = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(AppError::unauthorized)?;
+
+        let claims = decode_access_token(access_token, app_state.app_config.get_jwt_secret())?;
+
+        let user = match app_state
+            .repository_container
+            .user_repo
+            .get_user_entity_by_id(claims.sub)
+            .await
+        {
+            Ok(user) => user,
+            Err(AppError::NotFound(_)) => return Err(AppError::unauthorized()),
+            Err(e) => return Err(e),
+        };
+
+        if !user.is_active {
+            return Err(AppError::unauthorized());
+        }
+
+        Ok(AuthUser { user })
+    }
+}
+
+/// Middleware that rejects unauthenticated requests.
+///
+/// The resolved `AuthUser` is stored in the request extensions, so handlers behind this
+/// middleware can extract it without validating the token a second time.
+///
+/// # Returns
+///
+/// The `Response` of the inner service, or 401 (Unauthorized).
+pub async fn require_auth(auth_user: AuthUser, mut request: Request, next: Next) -> Response {
+    request.extensions_mut().insert(auth_user);
+    next.run(request).await
+}
+
+/// The IP address a request comes from.
+///
+/// Extracting `ClientIp` never fails. The address is the peer address of the connection, or
+/// `None` when the server is not serving with connect info.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(ClientIp(
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(address)| address.ip()),
+        ))
+    }
+}
+
+/// Who is making a request and where it comes from, as recorded in the audit log.
+///
+/// Extracting `AuditContext` never fails. Behind `require_auth` the actor is the authenticated
+/// caller; elsewhere it is `None`. The IP address is the peer address of the connection.
+///
+/// # Fields
+///
+/// * `actor_id` - The UUID of the authenticated caller, if any.
+/// * `ip_address` - The IP address of the client, if known.
+/// * `request_id` - The ID of the request, if known.
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuditContext {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ClientIp(ip_address) = ClientIp::from_request_parts(parts, state).await?;
+
+        Ok(AuditContext {
+            actor_id: parts
+                .extensions
+                .get::<AuthUser>()
+                .map(|auth_user| auth_user.user.id),
+            ip_address: ip_address.map(|ip_address| ip_address.to_string()),
+            request_id: current_request_id(),
+        })
+    }
+}
@@ -0,0 +1,11 @@
+/// Module for password hashing and verification.
+pub mod password;
+
+/// Module for access and refresh token handling.
+pub mod token;
+
+/// Module for the authenticated user extractor and middleware.
+pub mod extractor;
+
+/// Module for permission-based authorization.
+pub mod permission;
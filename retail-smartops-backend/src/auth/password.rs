@@ -0,0 +1,76 @@
+use crate::errors::AppError;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use rand::rngs::OsRng;
+
+/// An argon2id hash, with the default parameters, of a password nobody knows.
+///
+/// Logins for unknown users are verified against it, so they cost as much as logins for
+/// known users and response times do not reveal which usernames exist.
+pub const DUMMY_PASSWORD_HASH: &str =
+    "$argon2id$v=19$m=19456,t=2,p=1$L8AyMDn8zv1dV4yJgZkFOQ$5GLqkBiq/SNuxrhJvZ5xn+DkXxbe8tofo7fTM026Kg4";
+
+/// Hashes a plaintext password using argon2id.
+///
+/// A random salt is generated for every call, so hashing the same password twice yields
+/// two different PHC strings.
+///
+/// # Arguments
+///
+/// * `password` - The plaintext password to hash.
+///
+/// # Returns
+///
+/// * `Result<String, AppError>` - The PHC formatted hash string or an `AppError`.
+pub fn hash_password(password: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    let password_hash = Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|e| AppError::InternalServerError(format!("Failed to hash password: {}", e)))?
+        .to_string();
+
+    Ok(password_hash)
+}
+
+/// Verifies a plaintext password against an argon2 PHC hash string.
+///
+/// # Arguments
+///
+/// * `password` - The plaintext password to verify.
+/// * `password_hash` - The PHC formatted hash string stored for the user.
+///
+/// # Returns
+///
+/// * `Result<bool, AppError>` - `Ok(true)` if the password matches, `Ok(false)` otherwise, or an `AppError`
+///   if the stored hash cannot be parsed.
+pub fn verify_password(password: &str, password_hash: &str) -> Result<bool, AppError> {
+    let parsed_hash = PasswordHash::new(password_hash)
+        .map_err(|e| AppError::InternalServerError(format!("Invalid password hash: {}", e)))?;
+
+    Ok(Argon2::default()
+        .verify_password(password.as_bytes(), &parsed_hash)
+        .is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dummy_hash_uses_the_default_parameters() {
+        let dummy = PasswordHash::new(DUMMY_PASSWORD_HASH).unwrap();
+        let fresh_hash = hash_password("Correct-Horse-42").unwrap();
+        let fresh = PasswordHash::new(&fresh_hash).unwrap();
+
+        assert_eq!(dummy.algorithm, fresh.algorithm);
+        assert_eq!(dummy.params, fresh.params);
+    }
+
+    #[test]
+    fn dummy_hash_matches_no_submitted_password() {
+        for password in ["", "password123", "dummy", "Correct-Horse-42"] {
+            assert!(!verify_password(password, DUMMY_PASSWORD_HASH).unwrap());
+        }
+    }
+}
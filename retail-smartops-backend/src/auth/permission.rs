@@ -0,0 +1,150 @@
+use crate::auth::extractor::AuthUser;
+use crate::errors::AppError;
+use crate::models::permission::PermissionResponseDTO;
+use crate::AppState;
+use axum::extract::{RawPathParams, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::future::Future;
+use std::pin::Pin;
+
+/// An action that can be performed on an entity.
+///
+/// Each action maps to one of the `can_*` flags of the `permissions` table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Read,
+    Write,
+    Update,
+    Delete,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [Action::Read, Action::Write, Action::Update, Action::Delete];
+
+    /// Checks whether a permission allows this action.
+    ///
+    /// # Arguments
+    ///
+    /// * `permission` - The permission to check.
+    ///
+    /// # Returns
+    ///
+    /// A `bool` which is `true` if the matching flag of the permission is set.
+    pub fn is_granted_by(&self, permission: &PermissionResponseDTO) -> bool {
+        match self {
+            Action::Read => permission.can_read,
+            Action::Write => permission.can_write,
+            Action::Update => permission.can_update,
+            Action::Delete => permission.can_delete,
+        }
+    }
+}
+
+/// Checks whether a permission allows an action that none of the held permissions allow.
+///
+/// This is the rule that keeps callers from handing out more than they hold, whether by granting
+/// a role, attaching a permission to a role or widening a permission.
+///
+/// # Arguments
+///
+/// * `permission` - The permission being handed out.
+/// * `held` - The permissions the caller holds on the same entity.
+///
+/// # Returns
+///
+/// A `bool` which is `true` if `permission` allows any action missing from `held`.
+pub fn exceeds_permissions(
+    permission: &PermissionResponseDTO,
+    held: &[PermissionResponseDTO],
+) -> bool {
+    Action::ALL.iter().any(|action| {
+        action.is_granted_by(permission)
+            && !held
+                .iter()
+                .any(|held_permission| action.is_granted_by(held_permission))
+    })
+}
+
+/// The name of the path parameter that scopes a route to a store.
+pub const STORE_ID_PARAM: &str = "store_id";
+
+type GuardFuture = Pin<Box<dyn Future<Output = Response> + Send>>;
+
+/// Reads the store a request is scoped to from its path parameters.
+///
+/// # Arguments
+///
+/// * `path_params` - The raw path parameters of the matched route.
+///
+/// # Returns
+///
+/// * `Result<Option<i32>, AppError>` - The store ID, `None` if the route has no `store_id`
+///   parameter, or `AppError::BadRequest` if it is not a valid ID.
+fn store_id_from_path(path_params: &RawPathParams) -> Result<Option<i32>, AppError> {
+    match path_params.iter().find(|(key, _)| *key == STORE_ID_PARAM) {
+        Some((_, value)) => value
+            .parse::<i32>()
+            .map(Some)
+            .map_err(|_| AppError::bad_request("invalid_store_id", "The store ID is not valid")),
+        None => Ok(None),
+    }
+}
+
+/// Builds a middleware that requires the caller to hold a permission.
+///
+/// The returned function is meant to be passed to `axum::middleware::from_fn_with_state`
+/// and applied with `route_layer`. It resolves the caller through `AuthUser` and responds
+/// with 403 (Forbidden) when none of the caller's roles allows `action` on `entity_name`.
+///
+/// Routes whose path has a `:store_id` parameter are scoped to that store: roles the caller
+/// holds in the store count alongside their global roles. On every other route only global
+/// roles count.
+///
+/// # Arguments
+///
+/// * `entity_name` - The name of the entity being protected.
+/// * `action` - The action the routes perform on the entity.
+///
+/// # Example
+///
+/// ```ignore
+/// Router::new()
+///     .route("/inventory", post(create_item))
+///     .route_layer(middleware::from_fn_with_state(
+///         app_state.clone(),
+///         require_permission("inventory", Action::Write),
+///     ))
+/// ```
+pub fn require_permission(
+    entity_name: &'static str,
+    action: Action,
+) -> impl Fn(State<AppState>, AuthUser, RawPathParams, Request, Next) -> GuardFuture
+       + Clone
+       + Send
+       + Sync
+       + 'static {
+    move |State(app_state): State<AppState>,
+          auth_user: AuthUser,
+          path_params: RawPathParams,
+          request: Request,
+          next: Next| {
+        Box::pin(async move {
+            let store_id = match store_id_from_path(&path_params) {
+                Ok(store_id) => store_id,
+                Err(e) => return e.into_response(),
+            };
+
+            if let Err(e) = app_state
+                .service_container
+                .permission_service
+                .authorize(auth_user.user.id, entity_name, action, store_id)
+                .await
+            {
+                return e.into_response();
+            }
+
+            next.run(request).await
+        })
+    }
+}
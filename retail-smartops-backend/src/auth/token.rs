@@ -0,0 +1,103 @@
+use crate::errors::{AppError, ErrorDetail};
+use chrono::Utc;
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Claims carried by an access token.
+///
+/// # Fields
+///
+/// * `sub` - The unique identifier of the user the token was issued to.
+/// * `iat` - The time the token was issued at, as a unix timestamp.
+/// * `exp` - The time the token expires at, as a unix timestamp.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// Issues a signed HS256 access token for a user.
+///
+/// # Arguments
+///
+/// * `user_id` - The unique identifier of the user.
+/// * `secret` - The secret used to sign the token.
+/// * `ttl` - The lifetime of the token in seconds.
+///
+/// # Returns
+///
+/// * `Result<String, AppError>` - The encoded access token or an `AppError`.
+pub fn generate_access_token(user_id: Uuid, secret: &str, ttl: u64) -> Result<String, AppError> {
+    let issued_at = Utc::now().timestamp();
+    let claims = Claims {
+        sub: user_id,
+        iat: issued_at,
+        exp: issued_at + ttl as i64,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .map_err(|e| AppError::InternalServerError(format!("Failed to encode access token: {}", e)))
+}
+
+/// Decodes and validates an access token.
+///
+/// The signature and the expiry of the token are both checked.
+///
+/// # Arguments
+///
+/// * `access_token` - The encoded access token.
+/// * `secret` - The secret the token was signed with.
+///
+/// # Returns
+///
+/// * `Result<Claims, AppError>` - The claims of the token or `AppError::Unauthorized` if it is invalid.
+pub fn decode_access_token(access_token: &str, secret: &str) -> Result<Claims, AppError> {
+    decode::<Claims>(
+        access_token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map(|token_data| token_data.claims)
+    .map_err(|_| {
+        AppError::Unauthorized(ErrorDetail::new(
+            "invalid_token",
+            "The access token is invalid or has expired",
+        ))
+    })
+}
+
+/// Generates a new opaque refresh token.
+///
+/// # Returns
+///
+/// A `String` containing 32 random bytes encoded as hex.
+pub fn generate_refresh_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Hashes a refresh token for storage.
+///
+/// Refresh tokens are high-entropy random values, so a single SHA-256 round is enough
+/// and allows the session to be looked up by hash.
+///
+/// # Arguments
+///
+/// * `refresh_token` - The refresh token to hash.
+///
+/// # Returns
+///
+/// A `String` containing the hex encoded SHA-256 digest of the token.
+pub fn hash_refresh_token(refresh_token: &str) -> String {
+    format!("{:x}", Sha256::digest(refresh_token.as_bytes()))
+}
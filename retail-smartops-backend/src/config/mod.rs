@@ -1,93 +1,460 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The configuration file read when `APP_CONFIG_FILE` is not set. It is optional.
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+/// The placeholder printed instead of secrets.
+const REDACTED: &str = "********";
+
+const DEFAULT_DATABASE_PORT: u16 = 5432;
+const DEFAULT_DATABASE_MAX_CONNECTIONS: u32 = 10;
+const DEFAULT_DATABASE_IDLE_TIMEOUT: u64 = 600;
+const DEFAULT_DATABASE_ACQUIRE_TIMEOUT: u64 = 30;
+const DEFAULT_DATABASE_RUN_MIGRATIONS: bool = true;
+const DEFAULT_SERVER_HOST: &str = "0.0.0.0";
+const DEFAULT_SERVER_PORT: u16 = 3000;
+const DEFAULT_SERVER_SHUTDOWN_TIMEOUT: u64 = 30;
+const DEFAULT_JWT_ACCESS_TOKEN_TTL: u64 = 900;
+const DEFAULT_JWT_REFRESH_TOKEN_TTL: u64 = 1_209_600;
+const DEFAULT_LOGIN_MAX_ATTEMPTS_PER_USERNAME: u32 = 5;
+const DEFAULT_LOGIN_MAX_ATTEMPTS_PER_IP: u32 = 50;
+const DEFAULT_LOGIN_ATTEMPT_WINDOW: u64 = 900;
+const DEFAULT_LOGIN_LOCKOUT_BASE: u64 = 30;
+const DEFAULT_LOGIN_LOCKOUT_MAX: u64 = 900;
+
+/// The longest login lockout and attempt window accepted, one day. Larger values are clamped.
+const MAX_LOGIN_DURATION: u64 = 86_400;
+
+/// The format of the log lines written to standard output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines, for local development.
+    #[default]
+    Text,
+    /// One JSON object per line, for log collectors.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A single configuration key that is missing or invalid.
+///
+/// # Fields
+///
+/// * `key` - The environment variable naming the setting, e.g. `DATABASE_PORT`.
+/// * `message` - What is wrong with the value.
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    pub key: String,
+    pub message: String,
+}
+
+/// The error returned when the configuration cannot be loaded.
+///
+/// Every missing or invalid key is reported, not only the first one.
+#[derive(Debug, Error)]
+pub struct ConfigError {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid configuration:")?;
+        for issue in &self.issues {
+            write!(f, "\n  {}: {}", issue.key, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// The layout of the configuration file.
+///
+/// Every key is optional in the file. The same structure is used to print the effective
+/// configuration, so the output of `--print-config` is a valid configuration file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    database: DatabaseSection,
+    server: ServerSection,
+    jwt: JwtSection,
+    login: LoginSection,
+    log: LogSection,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DatabaseSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    max_connections: Option<u32>,
+    idle_timeout: Option<u64>,
+    acquire_timeout: Option<u64>,
+    run_migrations: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServerSection {
+    host: Option<String>,
+    port: Option<u16>,
+    shutdown_timeout: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct JwtSection {
+    secret: Option<String>,
+    access_token_ttl: Option<u64>,
+    refresh_token_ttl: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LoginSection {
+    max_attempts_per_username: Option<u32>,
+    max_attempts_per_ip: Option<u32>,
+    attempt_window: Option<u64>,
+    lockout_base: Option<u64>,
+    lockout_max: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LogSection {
+    format: Option<LogFormat>,
+}
+
+/// Resolves configuration values across the layers and records every problem found.
+struct ConfigLoader {
+    issues: Vec<ConfigIssue>,
+}
+
+impl ConfigLoader {
+    fn new() -> Self {
+        Self { issues: Vec::new() }
+    }
+
+    fn issue(&mut self, key: &str, message: impl Into<String>) {
+        self.issues.push(ConfigIssue {
+            key: key.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Resolves a value: the environment variable `key` wins over the value from the file.
+    fn optional<T: FromStr>(&mut self, key: &str, file_value: Option<T>) -> Option<T> {
+        match std::env::var(key) {
+            Ok(value) => match value.parse::<T>() {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    self.issue(key, format!("invalid value `{value}`"));
+                    None
+                }
+            },
+            Err(std::env::VarError::NotUnicode(_)) => {
+                self.issue(key, "value is not valid unicode");
+                None
+            }
+            Err(std::env::VarError::NotPresent) => file_value,
+        }
+    }
+
+    /// Resolves a value that has no default.
+    fn required<T: FromStr>(&mut self, key: &str, file_value: Option<T>) -> Option<T> {
+        let was_invalid = self.issues.len();
+        let value = self.optional(key, file_value);
+        if value.is_none() && self.issues.len() == was_invalid {
+            self.issue(key, "must be set");
+        }
+        value
+    }
+
+    /// Resolves a value that must be positive, falling back to a default.
+    fn positive<T: FromStr + PartialOrd + Default>(
+        &mut self,
+        key: &str,
+        file_value: Option<T>,
+        default: T,
+    ) -> T {
+        let value = self.optional(key, file_value).unwrap_or(default);
+        if value <= T::default() {
+            self.issue(key, "must be greater than zero");
+        }
+        value
+    }
+}
+
 /// Configuration for the application.
 ///
-/// This struct holds the configuration values for the database and server.
-/// The values are loaded from environment variables.
+/// This struct holds the configuration values for the database and server. Values are
+/// resolved from the following layers, each overriding the previous one:
+///
+/// 1. Built-in defaults for pool sizing, timeouts, migrations, the server address and its
+///    shutdown timeout, token lifetimes, login throttling and the log format.
+/// 2. The TOML file named by `APP_CONFIG_FILE`, or `config.toml` if it exists.
+/// 3. The `.env` file.
+/// 4. The process environment.
+///
+/// The database can be configured either with a single `DATABASE_URL` or with its parts
+/// (`DATABASE_USERNAME`, `DATABASE_PASSWORD`, `DATABASE_HOST`, `DATABASE_PORT` and
+/// `DATABASE_NAME`). The URL wins when both are present.
 pub struct AppConfig {
-    database_username: String,
-    database_password: String,
-    database_host: String,
-    database_port: u16,
-    database_name: String,
+    database_url: String,
     database_max_connections: u32,
     database_idle_timeout: u64,
+    database_acquire_timeout: u64,
+    database_run_migrations: bool,
     server_host: String,
     server_port: u16,
+    server_shutdown_timeout: u64,
+    jwt_secret: String,
+    jwt_access_token_ttl: u64,
+    jwt_refresh_token_ttl: u64,
+    login_max_attempts_per_username: u32,
+    login_max_attempts_per_ip: u32,
+    login_attempt_window: u64,
+    login_lockout_base: u64,
+    login_lockout_max: u64,
+    log_format: LogFormat,
 }
 
 impl AppConfig {
-    /// Loads the application configuration from environment variables.
-    ///
-    /// This function uses the `dotenvy` crate to load environment variables from a `.env` file,
-    /// and then reads the necessary configuration values from the environment.
-    ///
-    /// # Panics
-    ///
-    /// This function will panic if any of the required environment variables are not set or
-    /// if they cannot be parsed into the expected types.
-    ///
-    /// # Returns
-    ///
-    /// An instance of `AppConfig` with the loaded configuration values.
-    pub fn load() -> Self {
-        use dotenvy::dotenv;
-        use std::env;
-
-        dotenv().ok();
-
-        let database_username =
-            env::var("DATABASE_USERNAME").expect("DATABASE_USERNAME must be set");
-        let database_password =
-            env::var("DATABASE_PASSWORD").expect("DATABASE_PASSWORD must be set");
-        let database_host = env::var("DATABASE_HOST").expect("DATABASE_HOST must be set");
-        let database_port = env::var("DATABASE_PORT")
-            .expect("DATABASE_PORT must be set")
-            .parse::<u16>()
-            .expect("DATABASE_PORT must be a valid number");
-        let database_name = env::var("DATABASE_NAME").expect("DATABASE_NAME must be set");
-        let database_max_connections = env::var("DATABASE_MAX_CONNECTIONS")
-            .expect("DATABASE_MAX_CONNECTIONS must be set")
-            .parse::<u32>()
-            .expect("DATABASE_MAX_CONNECTIONS must be a valid number");
-        let database_idle_timeout = env::var("DATABASE_IDLE_TIMEOUT")
-            .expect("DATABASE_IDLE_TIMEOUT must be set")
-            .parse::<u64>()
-            .expect("DATABASE_IDLE_TIMEOUT must be a valid number");
-        let server_host = env::var("SERVER_HOST").expect("SERVER_HOST must be set");
-        let server_port = env::var("SERVER_PORT")
-            .expect("SERVER_PORT must be set")
-            .parse::<u16>()
-            .expect("SERVER_PORT must be a valid number");
+    /// Loads the application configuration from its layers.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<AppConfig, ConfigError>` - The configuration, or a `ConfigError` listing every
+    ///   missing or invalid key.
+    pub fn load() -> Result<Self, ConfigError> {
+        // `.env` never overrides variables already set in the environment.
+        dotenvy::dotenv().ok();
 
-        Self {
-            database_username,
-            database_password,
-            database_host,
-            database_port,
-            database_name,
-            database_max_connections,
-            database_idle_timeout,
-            server_host,
-            server_port,
+        let mut loader = ConfigLoader::new();
+
+        let config_file = match read_config_file() {
+            Ok(config_file) => config_file,
+            Err(issue) => {
+                loader.issues.push(issue);
+                ConfigFile::default()
+            }
+        };
+
+        let ConfigFile {
+            database,
+            server,
+            jwt,
+            login,
+            log,
+        } = config_file;
+
+        let database_url = match loader.optional("DATABASE_URL", database.url) {
+            Some(database_url) => Some(database_url),
+            None => {
+                let username = loader.required("DATABASE_USERNAME", database.username);
+                let password = loader
+                    .optional("DATABASE_PASSWORD", database.password)
+                    .unwrap_or_default();
+                let host = loader.required("DATABASE_HOST", database.host);
+                let port = loader
+                    .optional("DATABASE_PORT", database.port)
+                    .unwrap_or(DEFAULT_DATABASE_PORT);
+                let name = loader.required("DATABASE_NAME", database.name);
+
+                match (username, host, name) {
+                    (Some(username), Some(host), Some(name)) => Some(format!(
+                        "postgres://{username}:{password}@{host}:{port}/{name}"
+                    )),
+                    _ => None,
+                }
+            }
+        };
+        let database_max_connections = loader.positive(
+            "DATABASE_MAX_CONNECTIONS",
+            database.max_connections,
+            DEFAULT_DATABASE_MAX_CONNECTIONS,
+        );
+        let database_idle_timeout = loader.positive(
+            "DATABASE_IDLE_TIMEOUT",
+            database.idle_timeout,
+            DEFAULT_DATABASE_IDLE_TIMEOUT,
+        );
+        let database_acquire_timeout = loader.positive(
+            "DATABASE_ACQUIRE_TIMEOUT",
+            database.acquire_timeout,
+            DEFAULT_DATABASE_ACQUIRE_TIMEOUT,
+        );
+        let database_run_migrations = loader
+            .optional("DATABASE_RUN_MIGRATIONS", database.run_migrations)
+            .unwrap_or(DEFAULT_DATABASE_RUN_MIGRATIONS);
+
+        let server_host = loader
+            .optional("SERVER_HOST", server.host)
+            .unwrap_or_else(|| DEFAULT_SERVER_HOST.to_string());
+        let server_port = loader
+            .optional("SERVER_PORT", server.port)
+            .unwrap_or(DEFAULT_SERVER_PORT);
+        let server_shutdown_timeout = loader.positive(
+            "SERVER_SHUTDOWN_TIMEOUT",
+            server.shutdown_timeout,
+            DEFAULT_SERVER_SHUTDOWN_TIMEOUT,
+        );
+
+        let jwt_secret: Option<String> = loader.required("JWT_SECRET", jwt.secret);
+        if jwt_secret.as_deref().is_some_and(str::is_empty) {
+            loader.issue("JWT_SECRET", "must not be empty");
+        }
+        let jwt_access_token_ttl = loader.positive(
+            "JWT_ACCESS_TOKEN_TTL",
+            jwt.access_token_ttl,
+            DEFAULT_JWT_ACCESS_TOKEN_TTL,
+        );
+        let jwt_refresh_token_ttl = loader.positive(
+            "JWT_REFRESH_TOKEN_TTL",
+            jwt.refresh_token_ttl,
+            DEFAULT_JWT_REFRESH_TOKEN_TTL,
+        );
+
+        let login_max_attempts_per_username = loader.positive(
+            "LOGIN_MAX_ATTEMPTS_PER_USERNAME",
+            login.max_attempts_per_username,
+            DEFAULT_LOGIN_MAX_ATTEMPTS_PER_USERNAME,
+        );
+        let login_max_attempts_per_ip = loader.positive(
+            "LOGIN_MAX_ATTEMPTS_PER_IP",
+            login.max_attempts_per_ip,
+            DEFAULT_LOGIN_MAX_ATTEMPTS_PER_IP,
+        );
+        let login_attempt_window = loader
+            .positive(
+                "LOGIN_ATTEMPT_WINDOW",
+                login.attempt_window,
+                DEFAULT_LOGIN_ATTEMPT_WINDOW,
+            )
+            .min(MAX_LOGIN_DURATION);
+        let login_lockout_base = loader
+            .positive(
+                "LOGIN_LOCKOUT_BASE",
+                login.lockout_base,
+                DEFAULT_LOGIN_LOCKOUT_BASE,
+            )
+            .min(MAX_LOGIN_DURATION);
+        let login_lockout_max = loader
+            .positive(
+                "LOGIN_LOCKOUT_MAX",
+                login.lockout_max,
+                DEFAULT_LOGIN_LOCKOUT_MAX,
+            )
+            .min(MAX_LOGIN_DURATION);
+        if login_lockout_max < login_lockout_base {
+            loader.issue(
+                "LOGIN_LOCKOUT_MAX",
+                "must not be less than LOGIN_LOCKOUT_BASE",
+            );
+        }
+
+        let log_format = loader
+            .optional("LOG_FORMAT", log.format)
+            .unwrap_or_default();
+
+        match (database_url, jwt_secret) {
+            (Some(database_url), Some(jwt_secret)) if loader.issues.is_empty() => Ok(Self {
+                database_url,
+                database_max_connections,
+                database_idle_timeout,
+                database_acquire_timeout,
+                database_run_migrations,
+                server_host,
+                server_port,
+                server_shutdown_timeout,
+                jwt_secret,
+                jwt_access_token_ttl,
+                jwt_refresh_token_ttl,
+                login_max_attempts_per_username,
+                login_max_attempts_per_ip,
+                login_attempt_window,
+                login_lockout_base,
+                login_lockout_max,
+                log_format,
+            }),
+            _ => Err(ConfigError {
+                issues: loader.issues,
+            }),
         }
     }
 
-    /// Constructs the database connection URL.
+    /// Renders the effective configuration as TOML with the database password and the JWT
+    /// secret redacted.
     ///
-    /// This function creates a connection URL for the PostgreSQL database using the loaded
-    /// configuration values.
+    /// # Returns
+    ///
+    /// A `String` that can be used as a configuration file once the secrets are filled in.
+    pub fn to_redacted_toml(&self) -> String {
+        let config_file = ConfigFile {
+            database: DatabaseSection {
+                url: Some(redact_url_password(&self.database_url)),
+                max_connections: Some(self.database_max_connections),
+                idle_timeout: Some(self.database_idle_timeout),
+                acquire_timeout: Some(self.database_acquire_timeout),
+                run_migrations: Some(self.database_run_migrations),
+                ..DatabaseSection::default()
+            },
+            server: ServerSection {
+                host: Some(self.server_host.clone()),
+                port: Some(self.server_port),
+                shutdown_timeout: Some(self.server_shutdown_timeout),
+            },
+            jwt: JwtSection {
+                secret: Some(REDACTED.to_string()),
+                access_token_ttl: Some(self.jwt_access_token_ttl),
+                refresh_token_ttl: Some(self.jwt_refresh_token_ttl),
+            },
+            login: LoginSection {
+                max_attempts_per_username: Some(self.login_max_attempts_per_username),
+                max_attempts_per_ip: Some(self.login_max_attempts_per_ip),
+                attempt_window: Some(self.login_attempt_window),
+                lockout_base: Some(self.login_lockout_base),
+                lockout_max: Some(self.login_lockout_max),
+            },
+            log: LogSection {
+                format: Some(self.log_format),
+            },
+        };
+
+        toml::to_string(&config_file).expect("configuration is always serializable")
+    }
+
+    /// Gets the database connection URL.
     ///
     /// # Returns
     ///
-    /// A `String` containing the database connection URL.
-    pub fn get_database_url(&self) -> String {
-        format!(
-            "postgres://{}:{}@{}:{}/{}",
-            self.database_username,
-            self.database_password,
-            self.database_host,
-            self.database_port,
-            self.database_name
-        )
+    /// A `&str` containing the database connection URL.
+    pub fn get_database_url(&self) -> &str {
+        &self.database_url
     }
 
     /// Constructs the server address.
@@ -101,6 +468,15 @@ impl AppConfig {
         format!("{}:{}", self.server_host, self.server_port)
     }
 
+    /// Gets how long in-flight requests may take to finish once a shutdown is requested.
+    ///
+    /// # Returns
+    ///
+    /// A `u64` representing the drain timeout in seconds.
+    pub fn get_shutdown_timeout(&self) -> u64 {
+        self.server_shutdown_timeout
+    }
+
     /// Gets the maximum number of database connections.
     ///
     /// # Returns
@@ -118,4 +494,297 @@ impl AppConfig {
     pub fn get_idle_timeout(&self) -> u64 {
         self.database_idle_timeout
     }
+
+    /// Gets how long to wait for a free database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `u64` representing the acquire timeout for database connections in seconds.
+    pub fn get_acquire_timeout(&self) -> u64 {
+        self.database_acquire_timeout
+    }
+
+    /// Gets whether pending migrations are applied at startup.
+    ///
+    /// # Returns
+    ///
+    /// A `bool` which is `true` if the server migrates the database before serving requests.
+    pub fn get_run_migrations(&self) -> bool {
+        self.database_run_migrations
+    }
+
+    /// Gets the secret used to sign access tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `&str` containing the JWT signing secret.
+    pub fn get_jwt_secret(&self) -> &str {
+        &self.jwt_secret
+    }
+
+    /// Gets the lifetime of access tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `u64` representing the access token lifetime in seconds.
+    pub fn get_access_token_ttl(&self) -> u64 {
+        self.jwt_access_token_ttl
+    }
+
+    /// Gets the lifetime of refresh tokens.
+    ///
+    /// # Returns
+    ///
+    /// A `u64` representing the refresh token lifetime in seconds.
+    pub fn get_refresh_token_ttl(&self) -> u64 {
+        self.jwt_refresh_token_ttl
+    }
+
+    /// Gets how many failed logins for a username trigger a lockout.
+    ///
+    /// # Returns
+    ///
+    /// A `u32` representing the number of failed attempts.
+    pub fn get_login_max_attempts_per_username(&self) -> u32 {
+        self.login_max_attempts_per_username
+    }
+
+    /// Gets how many failed logins from an IP address trigger a lockout.
+    ///
+    /// # Returns
+    ///
+    /// A `u32` representing the number of failed attempts.
+    pub fn get_login_max_attempts_per_ip(&self) -> u32 {
+        self.login_max_attempts_per_ip
+    }
+
+    /// Gets how long a failed login is remembered. The count starts over once no login has
+    /// failed and no lockout has ended for this long.
+    ///
+    /// # Returns
+    ///
+    /// A `u64` representing the window in seconds.
+    pub fn get_login_attempt_window(&self) -> u64 {
+        self.login_attempt_window
+    }
+
+    /// Gets the duration of the first lockout. Every further failed attempt doubles it.
+    ///
+    /// # Returns
+    ///
+    /// A `u64` representing the lockout duration in seconds.
+    pub fn get_login_lockout_base(&self) -> u64 {
+        self.login_lockout_base
+    }
+
+    /// Gets the longest lockout.
+    ///
+    /// # Returns
+    ///
+    /// A `u64` representing the lockout duration in seconds.
+    pub fn get_login_lockout_max(&self) -> u64 {
+        self.login_lockout_max
+    }
+
+    /// Gets the format of the log lines.
+    ///
+    /// # Returns
+    ///
+    /// A `LogFormat` telling whether logs are written as text or JSON.
+    pub fn get_log_format(&self) -> LogFormat {
+        self.log_format
+    }
+}
+
+/// Reads the configuration file.
+///
+/// A missing file is only an error when `APP_CONFIG_FILE` names it explicitly.
+///
+/// # Returns
+///
+/// * `Result<ConfigFile, ConfigIssue>` - The parsed file, an empty one if there is no file,
+///   or the issue that prevented reading it.
+fn read_config_file() -> Result<ConfigFile, ConfigIssue> {
+    let (path, is_explicit) = match std::env::var_os("APP_CONFIG_FILE") {
+        Some(path) => (PathBuf::from(path), true),
+        None => (PathBuf::from(DEFAULT_CONFIG_FILE), false),
+    };
+
+    if !is_explicit && !Path::new(&path).exists() {
+        return Ok(ConfigFile::default());
+    }
+
+    let issue = |message: String| ConfigIssue {
+        key: "APP_CONFIG_FILE".to_string(),
+        message: format!("{}: {message}", path.display()),
+    };
+
+    let contents = std::fs::read_to_string(&path).map_err(|e| issue(e.to_string()))?;
+    toml::from_str(&contents).map_err(|e| issue(e.message().to_string()))
+}
+
+/// Replaces the password of a connection URL, if it has one.
+fn redact_url_password(url: &str) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_string();
+    };
+    let Some((user_info, host)) = rest.rsplit_once('@') else {
+        return url.to_string();
+    };
+
+    match user_info.split_once(':') {
+        Some((username, _)) => format!("{scheme}://{username}:{REDACTED}@{host}"),
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+impl AppConfig {
+    /// Builds a configuration with the built-in defaults and a fixed JWT secret, without reading
+    /// the environment. The database URL is a placeholder; tests pass their own pool.
+    pub(crate) fn for_tests() -> Self {
+        Self {
+            database_url: "postgres://localhost/test".to_string(),
+            database_max_connections: DEFAULT_DATABASE_MAX_CONNECTIONS,
+            database_idle_timeout: DEFAULT_DATABASE_IDLE_TIMEOUT,
+            database_acquire_timeout: DEFAULT_DATABASE_ACQUIRE_TIMEOUT,
+            database_run_migrations: DEFAULT_DATABASE_RUN_MIGRATIONS,
+            server_host: DEFAULT_SERVER_HOST.to_string(),
+            server_port: DEFAULT_SERVER_PORT,
+            server_shutdown_timeout: DEFAULT_SERVER_SHUTDOWN_TIMEOUT,
+            jwt_secret: "test-secret".to_string(),
+            jwt_access_token_ttl: DEFAULT_JWT_ACCESS_TOKEN_TTL,
+            jwt_refresh_token_ttl: DEFAULT_JWT_REFRESH_TOKEN_TTL,
+            login_max_attempts_per_username: DEFAULT_LOGIN_MAX_ATTEMPTS_PER_USERNAME,
+            login_max_attempts_per_ip: DEFAULT_LOGIN_MAX_ATTEMPTS_PER_IP,
+            login_attempt_window: DEFAULT_LOGIN_ATTEMPT_WINDOW,
+            login_lockout_base: DEFAULT_LOGIN_LOCKOUT_BASE,
+            login_lockout_max: DEFAULT_LOGIN_LOCKOUT_MAX,
+            log_format: LogFormat::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Every test uses its own variable names, as the environment is shared by parallel tests.
+
+    #[test]
+    fn environment_overrides_the_file() {
+        std::env::set_var("CONFIG_TEST_OVERRIDE_PORT", "4000");
+        let mut loader = ConfigLoader::new();
+
+        let port = loader.optional::<u16>("CONFIG_TEST_OVERRIDE_PORT", Some(3000));
+
+        assert_eq!(port, Some(4000));
+        assert!(loader.issues.is_empty());
+    }
+
+    #[test]
+    fn file_value_is_used_without_environment() {
+        let mut loader = ConfigLoader::new();
+
+        let port = loader.optional::<u16>("CONFIG_TEST_UNSET_PORT", Some(3000));
+        let host = loader.optional::<String>("CONFIG_TEST_UNSET_HOST", None);
+
+        assert_eq!(port, Some(3000));
+        assert_eq!(host, None);
+        assert!(loader.issues.is_empty());
+    }
+
+    #[test]
+    fn invalid_value_is_reported_once() {
+        std::env::set_var("CONFIG_TEST_INVALID_PORT", "eighty");
+        let mut loader = ConfigLoader::new();
+
+        let port = loader.required::<u16>("CONFIG_TEST_INVALID_PORT", Some(3000));
+
+        assert_eq!(port, None);
+        assert_eq!(loader.issues.len(), 1);
+        assert_eq!(loader.issues[0].key, "CONFIG_TEST_INVALID_PORT");
+        assert_eq!(loader.issues[0].message, "invalid value `eighty`");
+    }
+
+    #[test]
+    fn missing_required_value_is_reported() {
+        let mut loader = ConfigLoader::new();
+
+        let name = loader.required::<String>("CONFIG_TEST_MISSING_NAME", None);
+
+        assert_eq!(name, None);
+        assert_eq!(loader.issues.len(), 1);
+        assert_eq!(loader.issues[0].message, "must be set");
+    }
+
+    #[test]
+    fn positive_value_falls_back_to_default_and_rejects_zero() {
+        std::env::set_var("CONFIG_TEST_ZERO_TIMEOUT", "0");
+        let mut loader = ConfigLoader::new();
+
+        let default = loader.positive::<u64>("CONFIG_TEST_UNSET_TIMEOUT", None, 30);
+        let zero = loader.positive::<u64>("CONFIG_TEST_ZERO_TIMEOUT", Some(10), 30);
+
+        assert_eq!(default, 30);
+        assert_eq!(zero, 0);
+        assert_eq!(loader.issues.len(), 1);
+        assert_eq!(loader.issues[0].key, "CONFIG_TEST_ZERO_TIMEOUT");
+        assert_eq!(loader.issues[0].message, "must be greater than zero");
+    }
+
+    #[test]
+    fn every_issue_is_collected_and_displayed() {
+        std::env::set_var("CONFIG_TEST_AGGREGATE_PORT", "-1");
+        let mut loader = ConfigLoader::new();
+
+        loader.required::<String>("CONFIG_TEST_AGGREGATE_SECRET", None);
+        loader.optional::<u16>("CONFIG_TEST_AGGREGATE_PORT", None);
+        let error = ConfigError {
+            issues: loader.issues,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "invalid configuration:\n  CONFIG_TEST_AGGREGATE_SECRET: must be set\n  \
+             CONFIG_TEST_AGGREGATE_PORT: invalid value `-1`"
+        );
+    }
+
+    #[test]
+    fn file_rejects_unknown_keys() {
+        let result = toml::from_str::<ConfigFile>("[database]\nmax_conections = 5\n");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn file_sections_are_optional() {
+        let config_file = toml::from_str::<ConfigFile>("[server]\nport = 8080\n").unwrap();
+
+        assert_eq!(config_file.server.port, Some(8080));
+        assert_eq!(config_file.database.url, None);
+        assert_eq!(config_file.log.format, None);
+    }
+
+    #[test]
+    fn redacts_url_password() {
+        assert_eq!(
+            redact_url_password("postgres://app:s3cr:et@db:5432/retail"),
+            "postgres://app:********@db:5432/retail"
+        );
+    }
+
+    #[test]
+    fn keeps_url_without_password() {
+        assert_eq!(
+            redact_url_password("postgres://app@db:5432/retail"),
+            "postgres://app@db:5432/retail"
+        );
+        assert_eq!(
+            redact_url_password("postgres://db:5432/retail"),
+            "postgres://db:5432/retail"
+        );
+        assert_eq!(redact_url_password("not a url"), "not a url");
+    }
 }
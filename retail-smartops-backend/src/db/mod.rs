@@ -1,11 +1,53 @@
 use crate::config::AppConfig;
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
 use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// The migrations of the `migrations/` directory, embedded at compile time.
+static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Errors raised while inspecting or changing the database schema.
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("the database schema is ahead of this build: migration {0} is applied but unknown")]
+    SchemaAhead(i64),
+    #[error(transparent)]
+    Migrate(#[from] MigrateError),
+}
+
+/// The state of a migration in the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// The migration is applied and matches the embedded script.
+    Applied,
+    /// The migration is not applied yet.
+    Pending,
+    /// The migration is applied, but its script has changed since.
+    Modified,
+    /// The migration is applied, but this build does not know it.
+    Unknown,
+}
+
+/// A migration and its state in the database.
+///
+/// # Fields
+///
+/// * `version` - The version of the migration, taken from its file name.
+/// * `description` - The description of the migration, taken from its file name.
+/// * `state` - Whether the migration is applied.
+#[derive(Debug, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+}
 
 /// Service for managing database connections.
 ///
-/// This struct holds a connection pool to the MySQL database.
+/// This struct holds a connection pool to the PostgreSQL database.
 pub struct DbService {
     pool: PgPool,
 }
@@ -13,7 +55,7 @@ pub struct DbService {
 impl DbService {
     /// Creates a new instance of `DbService`.
     ///
-    /// This asynchronous function initializes a connection pool to the MySQL database
+    /// This asynchronous function initializes a connection pool to the PostgreSQL database
     /// using the provided application configuration.
     ///
     /// # Arguments
@@ -31,7 +73,8 @@ impl DbService {
         let pool = PgPoolOptions::new()
             .max_connections(app_config.get_max_connections())
             .idle_timeout(Duration::from_secs(app_config.get_idle_timeout()))
-            .connect(&app_config.get_database_url())
+            .acquire_timeout(Duration::from_secs(app_config.get_acquire_timeout()))
+            .connect(app_config.get_database_url())
             .await
             .expect("Failed to create database connection pool");
         Self { pool }
@@ -39,12 +82,201 @@ impl DbService {
 
     /// Retrieves the connection pool.
     ///
-    /// This function returns a clone of the MySQL connection pool.
+    /// This function returns a clone of the PostgreSQL connection pool.
     ///
     /// # Returns
     ///
-    /// A `MySqlPool` instance representing the connection pool.
+    /// A `PgPool` instance representing the connection pool.
     pub fn get_pool(&self) -> PgPool {
         self.pool.clone()
     }
+
+    /// Closes the connection pool.
+    ///
+    /// New acquisitions fail immediately; this waits until every checked-out connection has
+    /// been returned, so transactions in progress are committed or rolled back first.
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    /// Checks that the database answers within a timeout and reads its schema version.
+    ///
+    /// Both queries share the timeout, so a database that accepts connections but hangs on
+    /// the migrations table is reported as unreachable as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for a connection and both answers.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(Duration, Option<i64>), String>` - The round-trip time of a trivial query and
+    ///   the latest applied migration, or why the database is unreachable.
+    pub async fn probe(&self, timeout: Duration) -> Result<(Duration, Option<i64>), String> {
+        let probe = async {
+            let started_at = Instant::now();
+            sqlx::query("SELECT 1").execute(&self.pool).await?;
+            let latency = started_at.elapsed();
+
+            let applied_version = self.latest_applied_migration().await?;
+            Ok::<_, sqlx::Error>((latency, applied_version))
+        };
+
+        match tokio::time::timeout(timeout, probe).await {
+            Ok(Ok(result)) => Ok(result),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err(format!("no answer within {} ms", timeout.as_millis())),
+        }
+    }
+
+    /// Gets the number of open connections and how many of them are idle.
+    ///
+    /// # Returns
+    ///
+    /// A `(u32, usize)` tuple with the pool size and the number of idle connections.
+    pub fn pool_usage(&self) -> (u32, usize) {
+        (self.pool.size(), self.pool.num_idle())
+    }
+
+    /// Gets the version of the latest migration embedded in this build.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<i64>` with the version, or `None` if the build has no migrations.
+    pub fn latest_known_migration(&self) -> Option<i64> {
+        MIGRATOR.iter().map(|migration| migration.version).max()
+    }
+
+    /// Gets the version of the latest migration applied to the database.
+    ///
+    /// Unlike `migration_status`, this never creates the migrations table.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<i64>, sqlx::Error>` - The version, `None` if no migration is applied,
+    ///   or a `sqlx::Error`.
+    pub async fn latest_applied_migration(&self) -> Result<Option<i64>, sqlx::Error> {
+        let has_migrations_table: bool =
+            sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+                .fetch_one(&self.pool)
+                .await?;
+        if !has_migrations_table {
+            return Ok(None);
+        }
+
+        sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Lists the embedded migrations, and any unknown applied ones, with their state.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<MigrationStatus>, MigrationError>` - The migrations ordered by version, or
+    ///   a `MigrationError`.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        let mut connection = self.pool.acquire().await.map_err(MigrateError::from)?;
+        connection.ensure_migrations_table().await?;
+
+        let mut applied: HashMap<i64, Vec<u8>> = connection
+            .list_applied_migrations()
+            .await?
+            .into_iter()
+            .map(|migration| (migration.version, migration.checksum.into_owned()))
+            .collect();
+
+        let mut statuses: Vec<MigrationStatus> = MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| {
+                let state = match applied.remove(&migration.version) {
+                    Some(checksum) if checksum == *migration.checksum => MigrationState::Applied,
+                    Some(_) => MigrationState::Modified,
+                    None => MigrationState::Pending,
+                };
+
+                MigrationStatus {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                    state,
+                }
+            })
+            .collect();
+
+        statuses.extend(applied.into_keys().map(|version| MigrationStatus {
+            version,
+            description: String::new(),
+            state: MigrationState::Unknown,
+        }));
+        statuses.sort_by_key(|status| status.version);
+
+        Ok(statuses)
+    }
+
+    /// Checks that the database has no migration this build does not know about.
+    ///
+    /// A newer build may have migrated the schema in ways this one cannot handle, so the
+    /// server refuses to start against it.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<MigrationStatus>, MigrationError>` - The migration statuses, or
+    ///   `MigrationError::SchemaAhead` naming the first unknown migration.
+    pub async fn check_schema_version(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        let statuses = self.migration_status().await?;
+
+        match statuses
+            .iter()
+            .find(|status| status.state == MigrationState::Unknown)
+        {
+            Some(status) => Err(MigrationError::SchemaAhead(status.version)),
+            None => Ok(statuses),
+        }
+    }
+
+    /// Applies every pending migration.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), MigrationError>` - `Ok(())` once the schema is up to date, or a `MigrationError`.
+    pub async fn run_migrations(&self) -> Result<(), MigrationError> {
+        self.check_schema_version().await?;
+        MIGRATOR.run(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Reverts the most recently applied migration with its down script.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<i64>, MigrationError>` - The version of the reverted migration, `None` if
+    ///   no migration is applied, or a `MigrationError`.
+    pub async fn revert_last_migration(&self) -> Result<Option<i64>, MigrationError> {
+        let applied_versions: Vec<i64> = self
+            .check_schema_version()
+            .await?
+            .into_iter()
+            .filter(|status| status.state != MigrationState::Pending)
+            .map(|status| status.version)
+            .collect();
+
+        let Some((&last, rest)) = applied_versions.split_last() else {
+            return Ok(None);
+        };
+
+        MIGRATOR
+            .undo(&self.pool, rest.last().copied().unwrap_or(0))
+            .await?;
+
+        Ok(Some(last))
+    }
+}
+
+#[cfg(test)]
+impl DbService {
+    /// Wraps an existing connection pool, such as the one `#[sqlx::test]` hands to a test.
+    pub(crate) fn from_pool(pool: PgPool) -> Self {
+        Self { pool }
+    }
 }
@@ -21,3 +21,6 @@ pub mod user_hierarchy;
 
 /// Module for store-user relationship entities and functionality.
 pub mod store_users;
+
+/// Module for session-related entities and functionality.
+pub mod session;
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Represents a login session in the system.
+///
+/// This struct is used to store the refresh token issued at login. Only a hash of the
+/// refresh token is stored, and a session is considered active until it expires or is revoked.
+/// It derives `Debug`, `Serialize`, `Deserialize`, and `sqlx::FromRow` for easy
+/// debugging, serialization, deserialization, and database row mapping.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Session {
+    /// The unique identifier of the session.
+    pub id: Uuid,
+    /// The unique identifier of the user who owns the session.
+    pub user_id: Uuid,
+    /// The SHA-256 hash of the refresh token.
+    pub refresh_token_hash: String,
+    /// The timestamp when the session was created.
+    pub created_at: DateTime<Utc>,
+    /// The timestamp when the session expires.
+    pub expires_at: DateTime<Utc>,
+    /// The timestamp when the session was revoked, if it was.
+    pub revoked_at: Option<DateTime<Utc>>,
+}
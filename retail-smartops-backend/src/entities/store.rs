@@ -27,10 +27,10 @@ pub struct Store {
 /// Represents an address in the system.
 ///
 /// This struct is used to store address information such as country, state, city, street,
-/// and zip code. It derives `Debug`, `Serialize`, `Deserialize`, and `sqlx::Type` for easy
-/// debugging, serialization, deserialization, and database type mapping.
-#[derive(Debug, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "store_address")]
+/// and zip code. It derives `Debug`, `Serialize`, and `Deserialize` for easy debugging,
+/// serialization, and deserialization. The `stores` table keeps the address in flat columns,
+/// which the store repository maps into this struct.
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Address {
     /// The country of the address.
     pub country: String,
@@ -1,11 +1,22 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Represents the relationship between stores and users in the system.
 ///
 /// This struct is used to map the relationship between stores and their associated users.
+/// A membership may be bounded by a start and an end date; outside of that period the user
+/// is not considered part of the store's staff.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct StoreUsers {
     /// The identifier of the store.
     pub store_id: i32,
     /// The identifier of the user associated with the store.
     pub user_id: Uuid,
+    /// The first day of the membership, or `None` if it starts immediately.
+    pub starts_on: Option<NaiveDate>,
+    /// The last day of the membership, or `None` if it is open-ended.
+    pub ends_on: Option<NaiveDate>,
+    /// The timestamp when the user was added to the store.
+    pub created_at: DateTime<Utc>,
 }
@@ -6,9 +6,9 @@ use uuid::Uuid;
 ///
 /// This struct is used to store user information such as ID, username, password,
 /// and timestamps for when the user was created and last updated.
-/// It derives `Debug`, `Serialize`, `Deserialize`, and `sqlx::FromRow` for easy
+/// It derives `Debug`, `Clone`, `Serialize`, `Deserialize`, and `sqlx::FromRow` for easy
 /// debugging, serialization, deserialization, and database row mapping.
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct User {
     /// The unique identifier of the user.
     pub id: Uuid,
@@ -24,4 +24,6 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
     /// Indicates if the user is active.
     pub is_active: bool,
+    /// The timestamp when the user was soft deleted, or `None` if the user is not deleted.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -13,6 +13,8 @@ pub struct UserRole {
     pub user_id: Uuid,
     /// The identifier of the role assigned to the user.
     pub role_id: i32,
+    /// The identifier of the store the role applies to, or `None` for a global assignment.
+    pub store_id: Option<i32>,
     /// The timestamp when the role was assigned to the user.
     pub assigned_at: DateTime<Utc>,
 }
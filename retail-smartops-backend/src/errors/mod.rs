@@ -1,52 +1,393 @@
-use axum::http;
+use crate::request_id::current_request_id;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::{HeaderValue, StatusCode};
 use axum::response::{IntoResponse, Response};
+use serde::Serialize;
 use thiserror::Error;
-use tracing::error;
+use tracing::{debug, error};
+
+/// The media type of error bodies, as defined by RFC 7807.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// The SQLSTATE Postgres reports when a unique constraint is violated.
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// The SQLSTATE Postgres reports when a foreign key constraint is violated.
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+/// A problem with a single field of a request payload.
+///
+/// # Fields
+///
+/// * `field` - The name of the field, as it appears in the payload.
+/// * `message` - A human-readable description of the problem.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// The client-facing details of an error.
+///
+/// # Fields
+///
+/// * `code` - A machine-readable code clients can branch on, e.g. `username_taken`.
+/// * `message` - A human-readable description of the error.
+/// * `field_errors` - The fields of the payload the error is about, if any.
+#[derive(Debug, Clone)]
+pub struct ErrorDetail {
+    pub code: &'static str,
+    pub message: String,
+    pub field_errors: Vec<FieldError>,
+}
+
+impl ErrorDetail {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            field_errors: Vec::new(),
+        }
+    }
+
+    /// Attaches a problem with a single field to the error.
+    pub fn with_field(mut self, field: impl Into<String>, message: impl Into<String>) -> Self {
+        self.field_errors.push(FieldError::new(field, message));
+        self
+    }
+
+    /// Attaches problems with several fields to the error.
+    pub fn with_field_errors(mut self, field_errors: Vec<FieldError>) -> Self {
+        self.field_errors.extend(field_errors);
+        self
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Internal Server Error")]
     InternalServerError(String),
-    #[error("Conflict")]
-    Conflict,
-    #[error("Bad Request")]
-    BadRequest,
-    #[error("Service Unavailable")]
-    ServiceUnavailable,
-    #[error("Too Many Requests")]
-    TooManyRequests,
-    #[error("Unprocessable Entity")]
-    UnprocessableEntity,
-    #[error("Not Found")]
-    NotFound,
-    #[error("Forbidden")]
-    Forbidden,
-    #[error("Unauthorized")]
-    Unauthorized,
+    #[error("Conflict: {}", .0.message)]
+    Conflict(ErrorDetail),
+    #[error("Bad Request: {}", .0.message)]
+    BadRequest(ErrorDetail),
+    #[error("Service Unavailable: {}", .0.message)]
+    ServiceUnavailable(ErrorDetail),
+    #[error("Too Many Requests: {}", .0.message)]
+    TooManyRequests(ErrorDetail),
+    #[error("Unprocessable Entity: {}", .0.message)]
+    UnprocessableEntity(ErrorDetail),
+    #[error("Not Found: {}", .0.message)]
+    NotFound(ErrorDetail),
+    #[error("Forbidden: {}", .0.message)]
+    Forbidden(ErrorDetail),
+    #[error("Unauthorized: {}", .0.message)]
+    Unauthorized(ErrorDetail),
     #[error("Database Error: {0}")]
     DbError(#[from] sqlx::Error),
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let status_code = match self {
+impl AppError {
+    pub fn conflict(code: &'static str, message: impl Into<String>) -> Self {
+        AppError::Conflict(ErrorDetail::new(code, message))
+    }
+
+    pub fn bad_request(code: &'static str, message: impl Into<String>) -> Self {
+        AppError::BadRequest(ErrorDetail::new(code, message))
+    }
+
+    pub fn service_unavailable(code: &'static str, message: impl Into<String>) -> Self {
+        AppError::ServiceUnavailable(ErrorDetail::new(code, message))
+    }
+
+    pub fn too_many_requests(code: &'static str, message: impl Into<String>) -> Self {
+        AppError::TooManyRequests(ErrorDetail::new(code, message))
+    }
+
+    pub fn unprocessable(code: &'static str, message: impl Into<String>) -> Self {
+        AppError::UnprocessableEntity(ErrorDetail::new(code, message))
+    }
+
+    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
+        AppError::NotFound(ErrorDetail::new(code, message))
+    }
+
+    pub fn forbidden() -> Self {
+        AppError::Forbidden(ErrorDetail::new(
+            "forbidden",
+            "You are not allowed to perform this action",
+        ))
+    }
+
+    pub fn unauthorized() -> Self {
+        AppError::Unauthorized(ErrorDetail::new(
+            "unauthorized",
+            "Authentication is required",
+        ))
+    }
+
+    /// Builds a 422 (Unprocessable Entity) error listing the invalid fields of a payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_errors` - The problems found in the payload.
+    pub fn validation(field_errors: Vec<FieldError>) -> Self {
+        AppError::UnprocessableEntity(
+            ErrorDetail::new("validation_failed", "The request payload is invalid")
+                .with_field_errors(field_errors),
+        )
+    }
+
+    /// Splits the error into its status code and client-facing details.
+    ///
+    /// Unique and foreign key violations surfacing from the database are caller mistakes and
+    /// map to 409 (Conflict) and 422 (Unprocessable Entity). Every other internal or database
+    /// error is logged and hidden behind a generic 500 (Internal Server Error).
+    fn into_parts(self) -> (StatusCode, ErrorDetail) {
+        match self {
             AppError::InternalServerError(e) => {
                 error!("Internal Server Error: {}", e);
-                http::StatusCode::INTERNAL_SERVER_ERROR
+                (StatusCode::INTERNAL_SERVER_ERROR, internal_error_detail())
+            }
+            AppError::Conflict(detail) => (StatusCode::CONFLICT, detail),
+            AppError::BadRequest(detail) => (StatusCode::BAD_REQUEST, detail),
+            AppError::ServiceUnavailable(detail) => (StatusCode::SERVICE_UNAVAILABLE, detail),
+            AppError::TooManyRequests(detail) => (StatusCode::TOO_MANY_REQUESTS, detail),
+            AppError::UnprocessableEntity(detail) => (StatusCode::UNPROCESSABLE_ENTITY, detail),
+            AppError::NotFound(detail) => (StatusCode::NOT_FOUND, detail),
+            AppError::Forbidden(detail) => (StatusCode::FORBIDDEN, detail),
+            AppError::Unauthorized(detail) => (StatusCode::UNAUTHORIZED, detail),
+            AppError::DbError(sqlx::Error::Database(db_error))
+                if db_error.code().as_deref() == Some(UNIQUE_VIOLATION) =>
+            {
+                debug!("Unique violation: {}", db_error);
+                let detail = ErrorDetail::new(
+                    "unique_violation",
+                    "A record with the same unique value already exists",
+                );
+                (StatusCode::CONFLICT, detail)
+            }
+            AppError::DbError(sqlx::Error::Database(db_error))
+                if db_error.code().as_deref() == Some(FOREIGN_KEY_VIOLATION) =>
+            {
+                debug!("Foreign key violation: {}", db_error);
+                let detail = ErrorDetail::new(
+                    "foreign_key_violation",
+                    "The request references a record that does not exist or is still referenced",
+                );
+                (StatusCode::UNPROCESSABLE_ENTITY, detail)
             }
-            AppError::Conflict => http::StatusCode::CONFLICT,
-            AppError::BadRequest => http::StatusCode::BAD_REQUEST,
-            AppError::ServiceUnavailable => http::StatusCode::SERVICE_UNAVAILABLE,
-            AppError::TooManyRequests => http::StatusCode::TOO_MANY_REQUESTS,
-            AppError::UnprocessableEntity => http::StatusCode::UNPROCESSABLE_ENTITY,
-            AppError::NotFound => http::StatusCode::NOT_FOUND,
-            AppError::Forbidden => http::StatusCode::FORBIDDEN,
-            AppError::Unauthorized => http::StatusCode::UNAUTHORIZED,
             AppError::DbError(e) => {
                 error!("Database Error: {}", e);
-                http::StatusCode::INTERNAL_SERVER_ERROR
+                (StatusCode::INTERNAL_SERVER_ERROR, internal_error_detail())
             }
+        }
+    }
+}
+
+fn internal_error_detail() -> ErrorDetail {
+    ErrorDetail::new("internal_error", "An unexpected error occurred")
+}
+
+/// An RFC 7807 problem details body.
+///
+/// # Fields
+///
+/// * `problem_type` - A URI identifying the problem type, `about:blank` for plain HTTP errors.
+/// * `title` - The reason phrase of the status code.
+/// * `status` - The HTTP status code.
+/// * `code` - The machine-readable error code.
+/// * `detail` - The human-readable error message.
+/// * `errors` - The field errors, omitted when there are none.
+/// * `request_id` - The ID of the request that failed, if known.
+#[derive(Debug, Serialize)]
+struct ProblemDetails {
+    #[serde(rename = "type")]
+    problem_type: &'static str,
+    title: &'static str,
+    status: u16,
+    code: &'static str,
+    detail: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<FieldError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status_code, detail) = self.into_parts();
+
+        let problem = ProblemDetails {
+            problem_type: "about:blank",
+            title: status_code.canonical_reason().unwrap_or("Error"),
+            status: status_code.as_u16(),
+            code: detail.code,
+            detail: detail.message,
+            errors: detail.field_errors,
+            request_id: current_request_id(),
         };
-        status_code.into_response()
+
+        let mut response = (status_code, axum::Json(problem)).into_response();
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_JSON));
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    async fn problem(error: AppError) -> (StatusCode, Option<String>, Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        (status, content_type, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn maps_variants_to_status_codes() {
+        let cases = [
+            (AppError::conflict("c", "m"), StatusCode::CONFLICT),
+            (AppError::bad_request("c", "m"), StatusCode::BAD_REQUEST),
+            (
+                AppError::service_unavailable("c", "m"),
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            (
+                AppError::too_many_requests("c", "m"),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                AppError::unprocessable("c", "m"),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (AppError::not_found("c", "m"), StatusCode::NOT_FOUND),
+            (AppError::forbidden(), StatusCode::FORBIDDEN),
+            (AppError::unauthorized(), StatusCode::UNAUTHORIZED),
+            (
+                AppError::InternalServerError("boom".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::DbError(sqlx::Error::RowNotFound),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+
+        for (error, status) in cases {
+            assert_eq!(error.into_parts().0, status);
+        }
+    }
+
+    #[tokio::test]
+    async fn renders_problem_json() {
+        let (status, content_type, body) =
+            problem(AppError::not_found("store_not_found", "Store not found")).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(content_type.as_deref(), Some(PROBLEM_JSON));
+        assert_eq!(
+            body,
+            json!({
+                "type": "about:blank",
+                "title": "Not Found",
+                "status": 404,
+                "code": "store_not_found",
+                "detail": "Store not found",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn lists_field_errors() {
+        let error = AppError::validation(vec![
+            FieldError::new("email", "must be a valid email address"),
+            FieldError::new("username", "must not be blank"),
+        ]);
+
+        let (status, _, body) = problem(error).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(
+            body["errors"],
+            json!([
+                { "field": "email", "message": "must be a valid email address" },
+                { "field": "username", "message": "must not be blank" },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn hides_internal_error_details() {
+        let (status, _, body) = problem(AppError::InternalServerError(
+            "connection string postgres://app:secret@db".to_string(),
+        ))
+        .await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["code"], "internal_error");
+        assert_eq!(body["detail"], "An unexpected error occurred");
+    }
+
+    /// Runs a statement against a fresh test database and returns the error it fails with.
+    async fn database_error(pool: &sqlx::PgPool, statement: &str) -> AppError {
+        sqlx::query(statement)
+            .execute(pool)
+            .await
+            .map(|_| ())
+            .map_err(AppError::DbError)
+            .unwrap_err()
+    }
+
+    #[sqlx::test]
+    async fn maps_unique_violations_to_conflict(pool: sqlx::PgPool) {
+        sqlx::query("INSERT INTO roles (name) VALUES ('auditor')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let error = database_error(&pool, "INSERT INTO roles (name) VALUES ('auditor')").await;
+
+        let (status, content_type, body) = problem(error).await;
+
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(content_type.as_deref(), Some(PROBLEM_JSON));
+        assert_eq!(body["code"], "unique_violation");
+    }
+
+    #[sqlx::test]
+    async fn maps_foreign_key_violations_to_unprocessable_entity(pool: sqlx::PgPool) {
+        let error = database_error(
+            &pool,
+            "INSERT INTO user_roles (user_id, role_id) VALUES (gen_random_uuid(), -1)",
+        )
+        .await;
+
+        let (status, content_type, body) = problem(error).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(content_type.as_deref(), Some(PROBLEM_JSON));
+        assert_eq!(body["code"], "foreign_key_violation");
     }
 }
@@ -0,0 +1,21 @@
+use crate::models::audit::AuditQuery;
+use crate::validation::ValidatedQuery;
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+
+/// #### List audit events handler.
+///
+/// ### Returns
+///
+/// A `Response` containing a page of audit events.
+pub async fn get_audit_events(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AuditQuery>,
+) -> Response {
+    app_state
+        .service_container
+        .audit_service
+        .get_audit_events(query)
+        .await
+}
@@ -0,0 +1,78 @@
+use crate::auth::extractor::{AuthUser, ClientIp};
+use crate::models::auth::{LoginDTO, RefreshTokenDTO};
+use crate::models::user::UserResponseDTO;
+use crate::validation::ValidatedJson;
+use crate::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+
+/// #### Login handler.
+///
+/// This asynchronous function verifies the submitted credentials and issues an access token
+/// and a refresh token.
+///
+/// ### Returns
+///
+/// A `Response` containing the issued tokens, 401 (Unauthorized), or 429 (Too Many Requests)
+/// while the username or the client IP address is locked out.
+pub async fn login(
+    State(app_state): State<AppState>,
+    ClientIp(ip_address): ClientIp,
+    ValidatedJson(payload): ValidatedJson<LoginDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .login_user(&payload.username, &payload.password, ip_address)
+        .await
+}
+
+/// #### Refresh handler.
+///
+/// This asynchronous function exchanges a refresh token for a new token pair.
+///
+/// ### Returns
+///
+/// A `Response` containing the new tokens, or 401 (Unauthorized).
+pub async fn refresh(
+    State(app_state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<RefreshTokenDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .refresh_session(&payload.refresh_token)
+        .await
+}
+
+/// #### Logout handler.
+///
+/// This asynchronous function revokes the session the refresh token belongs to.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content), or 401 (Unauthorized).
+pub async fn logout(
+    State(app_state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<RefreshTokenDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .logout_user(&payload.refresh_token)
+        .await
+}
+
+/// #### Current user handler.
+///
+/// This asynchronous function returns the user the access token was issued to.
+///
+/// ### Returns
+///
+/// A `Response` containing the current user.
+pub async fn current_user(auth_user: AuthUser) -> Response {
+    let response = Json(UserResponseDTO::from(auth_user.user));
+    (StatusCode::OK, response).into_response()
+}
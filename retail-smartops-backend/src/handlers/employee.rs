@@ -0,0 +1,76 @@
+use crate::auth::extractor::{AuditContext, AuthUser};
+use crate::models::user::UpdateUserDTO;
+use crate::validation::{ValidatedJson, ValidatedPath};
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// #### Get employee handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the requested employee.
+pub async fn get_employee(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .get_employee_by_id(id)
+        .await
+}
+
+/// #### Update employee handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the updated employee.
+pub async fn update_employee(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdateUserDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .update_employee(id, payload, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Deactivate employee handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the deactivation event.
+pub async fn deactivate_employee(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .deactivate_employee(id, &audit)
+        .await
+}
+
+/// #### Reactivate employee handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the reactivation event.
+pub async fn reactivate_employee(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .reactive_employee(id, &audit)
+        .await
+}
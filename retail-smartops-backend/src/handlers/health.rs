@@ -1,3 +1,5 @@
+use crate::AppState;
+use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -18,3 +20,21 @@ pub async fn health_check() -> Response {
     }));
     (status_code, response).into_response()
 }
+
+/// #### Liveness probe handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 200 (OK) while the process is running.
+pub async fn liveness(State(app_state): State<AppState>) -> Response {
+    app_state.service_container.health_service.liveness().await
+}
+
+/// #### Readiness probe handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the readiness report, or 503 (Service Unavailable).
+pub async fn readiness(State(app_state): State<AppState>) -> Response {
+    app_state.service_container.health_service.readiness().await
+}
@@ -0,0 +1,23 @@
+use crate::auth::extractor::AuditContext;
+use crate::models::auth::UnlockLoginDTO;
+use crate::validation::ValidatedJson;
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+
+/// #### Unlock login handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content), or 404 (Not Found) if nothing was locked out.
+pub async fn unlock_login(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<UnlockLoginDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .unlock_login(payload, &audit)
+        .await
+}
@@ -0,0 +1,12 @@
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+
+/// #### Metrics handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the metrics in the Prometheus text format.
+pub async fn metrics(State(app_state): State<AppState>) -> Response {
+    app_state.service_container.metrics_service.render().await
+}
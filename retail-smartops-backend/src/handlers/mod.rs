@@ -1 +1,12 @@
+pub mod audit;
+pub mod auth;
+pub mod employee;
 pub mod health;
+pub mod login_lockout;
+pub mod metrics;
+pub mod permission;
+pub mod role;
+pub mod store;
+pub mod user;
+pub mod user_hierarchy;
+pub mod user_role;
@@ -0,0 +1,139 @@
+use crate::auth::extractor::{AuditContext, AuthUser};
+use crate::models::permission::{CreatePermissionDTO, UpdatePermissionDTO};
+use crate::validation::{ValidatedJson, ValidatedPath};
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+
+/// #### Create permission handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the created permission.
+pub async fn create_permission(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<CreatePermissionDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .create_permission(payload, &audit)
+        .await
+}
+
+/// #### Get permission handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the requested permission.
+pub async fn get_permission(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .get_permission(id)
+        .await
+}
+
+/// #### Update permission handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the updated permission.
+pub async fn update_permission(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdatePermissionDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .update_permission(id, payload, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Delete permission handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn delete_permission(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .delete_permission(id, &audit)
+        .await
+}
+
+/// #### List permissions handler.
+///
+/// ### Returns
+///
+/// A `Response` containing every permission.
+pub async fn get_permissions(State(app_state): State<AppState>) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .get_permissions()
+        .await
+}
+
+/// #### Grant role permission handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the created role permission.
+pub async fn grant_role_permission(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath((role_id, permission_id)): ValidatedPath<(i32, i32)>,
+) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .grant_role_permission(role_id, permission_id, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Revoke role permission handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn revoke_role_permission(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath((role_id, permission_id)): ValidatedPath<(i32, i32)>,
+) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .revoke_role_permission(role_id, permission_id, &audit)
+        .await
+}
+
+/// #### List role permissions handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the permissions of the role.
+pub async fn get_role_permissions(
+    State(app_state): State<AppState>,
+    ValidatedPath(role_id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .permission_service
+        .get_role_permissions(role_id)
+        .await
+}
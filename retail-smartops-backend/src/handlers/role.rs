@@ -0,0 +1,87 @@
+use crate::auth::extractor::AuditContext;
+use crate::models::list::ListQuery;
+use crate::models::role::{CreateRoleDTO, UpdateRoleDTO};
+use crate::validation::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+
+/// #### Create role handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the created role.
+pub async fn create_role(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<CreateRoleDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .role_service
+        .create_role(payload, &audit)
+        .await
+}
+
+/// #### Get role handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the requested role.
+pub async fn get_role(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Response {
+    app_state.service_container.role_service.get_role(id).await
+}
+
+/// #### Update role handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the updated role.
+pub async fn update_role(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateRoleDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .role_service
+        .update_role(id, payload, &audit)
+        .await
+}
+
+/// #### Delete role handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn delete_role(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .role_service
+        .delete_role(id, &audit)
+        .await
+}
+
+/// #### List roles handler.
+///
+/// ### Returns
+///
+/// A `Response` containing a page of roles.
+pub async fn get_roles(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListQuery>,
+) -> Response {
+    app_state
+        .service_container
+        .role_service
+        .get_roles(query)
+        .await
+}
@@ -0,0 +1,161 @@
+use crate::auth::extractor::{AuditContext, AuthUser};
+use crate::models::list::ListQuery;
+use crate::models::store::{CreateStoreDTO, UpdateStoreDTO};
+use crate::models::store_users::AddStoreUserDTO;
+use crate::validation::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// #### Create store handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the created store.
+pub async fn create_store(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<CreateStoreDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .create_store(payload, &audit)
+        .await
+}
+
+/// #### Get store handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the requested store.
+pub async fn get_store(
+    State(app_state): State<AppState>,
+    ValidatedPath(store_id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .get_store(store_id)
+        .await
+}
+
+/// #### Update store handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the updated store.
+pub async fn update_store(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath(store_id): ValidatedPath<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateStoreDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .update_store(store_id, payload, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Delete store handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn delete_store(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(store_id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .delete_store(store_id, &audit)
+        .await
+}
+
+/// #### List stores handler.
+///
+/// ### Returns
+///
+/// A `Response` containing a page of stores.
+pub async fn get_stores(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListQuery>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .get_stores(query)
+        .await
+}
+
+/// #### List store staff handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the current staff of the store.
+pub async fn get_store_staff(
+    State(app_state): State<AppState>,
+    ValidatedPath(store_id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .get_store_staff(store_id)
+        .await
+}
+
+/// #### Add store user handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the created membership.
+pub async fn add_store_user(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(store_id): ValidatedPath<i32>,
+    ValidatedJson(payload): ValidatedJson<AddStoreUserDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .add_store_user(store_id, payload, &audit)
+        .await
+}
+
+/// #### Remove store user handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn remove_store_user(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath((store_id, user_id)): ValidatedPath<(i32, Uuid)>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .remove_store_user(store_id, user_id, &audit)
+        .await
+}
+
+/// #### List user stores handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the stores the user currently works at.
+pub async fn get_user_stores(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .store_service
+        .get_user_stores(id)
+        .await
+}
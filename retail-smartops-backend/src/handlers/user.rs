@@ -0,0 +1,128 @@
+use crate::auth::extractor::{AuditContext, AuthUser};
+use crate::models::list::ListQuery;
+use crate::models::user::{CreateUserDTO, PurgeUserQuery, UpdateUserDTO};
+use crate::validation::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// #### Create user handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the created user.
+pub async fn create_user(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedJson(payload): ValidatedJson<CreateUserDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .register_user(payload, &audit)
+        .await
+}
+
+/// #### Get user handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the requested user.
+pub async fn get_user(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .get_user_by_id(id)
+        .await
+}
+
+/// #### Update user handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the updated user.
+pub async fn update_user(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdateUserDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .update_user(id, payload, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Delete user handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn delete_user(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .delete_user(id, &audit)
+        .await
+}
+
+/// #### List users handler.
+///
+/// ### Returns
+///
+/// A `Response` containing a page of users.
+pub async fn get_users(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListQuery>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .get_users(query)
+        .await
+}
+
+/// #### Restore user handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the restored user.
+pub async fn restore_user(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .restore_user(id, &audit)
+        .await
+}
+
+/// #### Purge user handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn purge_user(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedQuery(query): ValidatedQuery<PurgeUserQuery>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .purge_user(id, query, &audit)
+        .await
+}
@@ -0,0 +1,101 @@
+use crate::auth::extractor::AuthUser;
+use crate::models::user_hierarchy::SetManagerDTO;
+use crate::validation::{ValidatedJson, ValidatedPath};
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// #### Set manager handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the reporting line of the user.
+pub async fn set_manager(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<SetManagerDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_hierarchy_service
+        .set_manager(id, payload)
+        .await
+}
+
+/// #### Remove manager handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn remove_manager(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_hierarchy_service
+        .remove_manager(id)
+        .await
+}
+
+/// #### List direct reports handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the users reporting directly to the user.
+pub async fn get_direct_reports(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_hierarchy_service
+        .get_direct_reports(id)
+        .await
+}
+
+/// #### List subordinates handler.
+///
+/// ### Returns
+///
+/// A `Response` containing every user below the user in the hierarchy.
+pub async fn get_subordinates(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_hierarchy_service
+        .get_subtree(id)
+        .await
+}
+
+/// #### Management chain handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the managers above the user.
+pub async fn get_management_chain(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_hierarchy_service
+        .get_management_chain(id)
+        .await
+}
+
+/// #### Current user team handler.
+///
+/// ### Returns
+///
+/// A `Response` containing every user reporting to the authenticated user.
+pub async fn my_team(State(app_state): State<AppState>, auth_user: AuthUser) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .get_employees(auth_user.user.id)
+        .await
+}
@@ -0,0 +1,116 @@
+use crate::auth::extractor::{AuditContext, AuthUser};
+use crate::models::user_role::{
+    AssignUserRoleDTO, ReplaceUserRoleDTO, SetUserRolesDTO, UserRoleScopeQuery,
+};
+use crate::validation::{ValidatedJson, ValidatedPath, ValidatedQuery};
+use crate::AppState;
+use axum::extract::State;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// #### List user roles handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the roles held by the user.
+pub async fn get_user_roles(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .get_user_roles(id)
+        .await
+}
+
+/// #### Assign user role handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the created role assignment.
+pub async fn assign_user_role(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<AssignUserRoleDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .assign_user_role(id, payload, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Replace user role handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the resulting roles of the user.
+pub async fn replace_user_role(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath((id, role_id)): ValidatedPath<(Uuid, i32)>,
+    ValidatedJson(payload): ValidatedJson<ReplaceUserRoleDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .replace_user_role(id, role_id, payload, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Set user roles handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the resulting roles of the user.
+pub async fn set_user_roles(
+    State(app_state): State<AppState>,
+    auth_user: AuthUser,
+    audit: AuditContext,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<SetUserRolesDTO>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .set_user_roles(id, payload, auth_user.user.id, &audit)
+        .await
+}
+
+/// #### Revoke user role handler.
+///
+/// ### Returns
+///
+/// A `Response` with status code 204 (No Content).
+pub async fn revoke_user_role(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    ValidatedPath((id, role_id)): ValidatedPath<(Uuid, i32)>,
+    ValidatedQuery(scope): ValidatedQuery<UserRoleScopeQuery>,
+) -> Response {
+    app_state
+        .service_container
+        .user_access_management_service
+        .revoke_user_role(id, role_id, scope.store_id, &audit)
+        .await
+}
+
+/// #### List role members handler.
+///
+/// ### Returns
+///
+/// A `Response` containing the users holding the role.
+pub async fn get_role_members(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<i32>,
+) -> Response {
+    app_state
+        .service_container
+        .role_service
+        .get_role_members(id)
+        .await
+}
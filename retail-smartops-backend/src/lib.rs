@@ -1,24 +1,46 @@
-use crate::config::AppConfig;
-use crate::db::DbService;
+use crate::auth::extractor::AuditContext;
+use crate::auth::password::hash_password;
+use crate::config::{AppConfig, LogFormat};
+use crate::db::{DbService, MigrationState};
+use crate::metrics::Metrics;
+use crate::models::audit::{AuditAction, AuditEntity};
+use crate::models::user::CreateUserDTO;
 use crate::repositories::RepositoryContainer;
 use crate::routes::create_app_routes;
+use crate::services::audit_service::{AuditEntry, AuditService};
 use crate::services::ServiceContainer;
+use crate::shutdown::{termination_requested, Shutdown};
+use crate::validation::field_errors;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+use validator::Validate;
 
+mod auth;
 mod config;
 mod db;
 mod entities;
 mod errors;
 mod handlers;
+mod metrics;
 mod models;
 mod repositories;
+mod request_id;
 mod routes;
 mod services;
+mod shutdown;
+#[cfg(test)]
+mod test_support;
+mod validation;
 
 /// Runs the application by setting up tracing, creating application routes, and starting the server.
 ///
+/// On Ctrl-C or SIGTERM the server stops accepting connections and waits up to
+/// `SERVER_SHUTDOWN_TIMEOUT` seconds for in-flight requests to finish, then the database pool
+/// is closed.
+///
 /// #### Arguments
 ///
 /// * `listener` - An optional `TcpListener` to bind the server to a specific address and port.
@@ -27,7 +49,104 @@ mod services;
 ///
 /// A `Result` which is `Ok` if the server runs successfully, or an `std::io::Error` if an error occurs.
 pub async fn run_app(listener: Option<TcpListener>) -> Result<(), std::io::Error> {
-    // Initialize the tracing subscriber with environment filter and formatting layer.
+    let app_config =
+        AppConfig::load().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    init_tracing(app_config.get_log_format());
+
+    let db_service = Arc::new(DbService::new(&app_config).await);
+
+    // Refuse to serve a schema written by a newer build, then bring the schema up to date.
+    let migration_statuses = db_service
+        .check_schema_version()
+        .await
+        .map_err(std::io::Error::other)?;
+    if app_config.get_run_migrations() {
+        db_service
+            .run_migrations()
+            .await
+            .map_err(std::io::Error::other)?;
+        tracing::info!("database migrations are up to date");
+    } else if migration_statuses
+        .iter()
+        .any(|status| status.state == MigrationState::Pending)
+    {
+        tracing::warn!("database has pending migrations and DATABASE_RUN_MIGRATIONS is off");
+    }
+
+    let repository_container = RepositoryContainer::new(db_service.get_pool());
+
+    let app_state = AppState::new(app_config, db_service.clone(), repository_container);
+
+    // Create application routes.
+    let app_routes = create_app_routes(app_state.clone());
+
+    // Bind the listener to the specified address and port, or default to "0.0.0.0:3000".
+    let listener = match listener {
+        Some(listener) => listener,
+        None => TcpListener::bind(app_state.app_config.get_server_address())
+            .await
+            .expect("Failed to bind port"),
+    };
+
+    // Turn Ctrl-C and SIGTERM into a shutdown of the whole application.
+    let shutdown = Shutdown::new();
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            termination_requested().await;
+            tracing::info!("shutdown requested, draining in-flight requests");
+            shutdown.trigger();
+        }
+    });
+
+    // Serve the application until the shutdown, then give in-flight requests time to finish.
+    let server = axum::serve(
+        listener,
+        app_routes.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown.subscribe().recv());
+    let drain_timeout = Duration::from_secs(app_state.app_config.get_shutdown_timeout());
+    let drain_deadline = async {
+        shutdown.subscribe().recv().await;
+        tokio::time::sleep(drain_timeout).await;
+    };
+
+    tokio::select! {
+        result = server => result?,
+        _ = drain_deadline => tracing::warn!(
+            "requests still in flight after {} s, shutting down anyway",
+            drain_timeout.as_secs()
+        ),
+    }
+
+    // Requests cut off by the deadline may still hold connections, so closing is bounded too.
+    if tokio::time::timeout(drain_timeout, db_service.close())
+        .await
+        .is_err()
+    {
+        tracing::warn!("database connections still in use, closing the pool anyway");
+    }
+    tracing::info!("shutdown complete");
+    Ok(())
+}
+
+/// Initializes the tracing subscriber with an environment filter and a formatting layer.
+///
+/// #### Arguments
+///
+/// * `log_format` - Whether log lines are written as text or as JSON objects.
+fn init_tracing(log_format: LogFormat) {
+    let fmt_layer = match log_format {
+        LogFormat::Text => fmt::layer().boxed(),
+        // Span fields such as the request ID are flattened into each line next to the event.
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .boxed(),
+    };
+
     tracing_subscriber::registry()
         .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| {
             format!(
@@ -36,44 +155,182 @@ pub async fn run_app(listener: Option<TcpListener>) -> Result<(), std::io::Error
             )
             .into()
         }))
-        .with(fmt::layer())
+        .with(fmt_layer)
         .init();
+}
 
-    let app_config = AppConfig::load();
-    let db_service = DbService::new(&app_config).await;
+/// Loads the configuration and prints it as TOML with secrets redacted.
+///
+/// #### Returns
+///
+/// A `Result` which is `Ok` if the configuration is valid, or an `std::io::Error` listing every
+/// invalid key otherwise.
+pub fn print_config() -> Result<(), std::io::Error> {
+    let app_config =
+        AppConfig::load().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    print!("{}", app_config.to_redacted_toml());
+    Ok(())
+}
 
-    let repository_container = RepositoryContainer::new(db_service.get_pool());
+/// Runs a migration admin command against the configured database.
+///
+/// * `status` lists every migration and whether it is applied.
+/// * `run` applies the pending migrations.
+/// * `revert` reverts the most recently applied migration.
+///
+/// #### Arguments
+///
+/// * `command` - The name of the command.
+///
+/// #### Returns
+///
+/// A `Result` which is `Ok` if the command succeeds, or an `std::io::Error` if an error occurs.
+pub async fn run_migrate_command(command: &str) -> Result<(), std::io::Error> {
+    let app_config =
+        AppConfig::load().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let db_service = DbService::new(&app_config).await;
 
-    let app_state = AppState::new(app_config, repository_container);
+    match command {
+        "status" => {
+            let statuses = db_service
+                .migration_status()
+                .await
+                .map_err(std::io::Error::other)?;
+            for status in statuses {
+                let state = match status.state {
+                    MigrationState::Applied => "applied",
+                    MigrationState::Pending => "pending",
+                    MigrationState::Modified => "modified",
+                    MigrationState::Unknown => "unknown",
+                };
+                println!("{:<16} {:<9} {}", status.version, state, status.description);
+            }
+        }
+        "run" => {
+            db_service
+                .run_migrations()
+                .await
+                .map_err(std::io::Error::other)?;
+            println!("database migrations are up to date");
+        }
+        "revert" => {
+            match db_service
+                .revert_last_migration()
+                .await
+                .map_err(std::io::Error::other)?
+            {
+                Some(version) => println!("reverted migration {version}"),
+                None => println!("no migration to revert"),
+            }
+        }
+        _ => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("unknown migrate command `{command}`, expected status, run or revert"),
+            ))
+        }
+    }
 
-    let _service_container = ServiceContainer::new(app_state.repository_container.clone());
+    Ok(())
+}
 
-    // Create application routes.
-    let app_routes = create_app_routes(app_state.clone());
+/// Creates the first administrator of a fresh deployment.
+///
+/// The user is created with the seeded `admin` role, held globally, so they can create every
+/// other user through the API. The command refuses to run once an active administrator exists.
+/// The password is read from the first line of standard input, to keep it out of the shell
+/// history and the process list.
+///
+/// #### Arguments
+///
+/// * `username` - The username of the administrator.
+/// * `email` - The email address of the administrator.
+///
+/// #### Returns
+///
+/// A `Result` which is `Ok` if the administrator was created, or an `std::io::Error` if an error occurs.
+pub async fn run_bootstrap_admin_command(
+    username: Option<&str>,
+    email: Option<&str>,
+) -> Result<(), std::io::Error> {
+    let (Some(username), Some(email)) = (username, email) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "usage: bootstrap-admin <username> <email>, with the password on standard input",
+        ));
+    };
 
-    // Bind the listener to the specified address and port, or default to "0.0.0.0:3000".
-    let listener = match listener {
-        Some(listener) => listener,
-        None => TcpListener::bind(app_state.app_config.get_server_address())
-            .await
-            .expect("Failed to bind port"),
+    let mut password = String::new();
+    std::io::stdin().read_line(&mut password)?;
+    let payload = CreateUserDTO {
+        username: username.to_string(),
+        email: email.to_string(),
+        password: password.trim_end_matches(['\r', '\n']).to_string(),
     };
+    if let Err(errors) = payload.validate() {
+        let issues: Vec<String> = field_errors(&errors)
+            .into_iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect();
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            issues.join("\n"),
+        ));
+    }
+    let password = hash_password(&payload.password).map_err(std::io::Error::other)?;
+
+    let app_config =
+        AppConfig::load().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let db_service = DbService::new(&app_config).await;
+    let repository_container = Arc::new(RepositoryContainer::new(db_service.get_pool()));
+
+    let user = repository_container
+        .user_repo
+        .create_first_admin(CreateUserDTO {
+            password,
+            ..payload
+        })
+        .await
+        .map_err(std::io::Error::other)?;
+    AuditService::new(repository_container)
+        .record(
+            &AuditContext::default(),
+            AuditEntry::new(AuditAction::Create, AuditEntity::User, user.id).with_after(&user),
+        )
+        .await;
 
-    // Serve the application using the specified listener and routes.
-    axum::serve(listener, app_routes.into_make_service()).await
+    println!("created administrator {} ({})", user.username, user.id);
+    Ok(())
 }
 
 #[derive(Clone)]
 pub struct AppState {
     app_config: Arc<AppConfig>,
     repository_container: Arc<RepositoryContainer>,
+    service_container: Arc<ServiceContainer>,
+    metrics: Arc<Metrics>,
 }
 
 impl AppState {
-    pub fn new(app_config: AppConfig, repository_container: RepositoryContainer) -> Self {
+    pub fn new(
+        app_config: AppConfig,
+        db_service: Arc<DbService>,
+        repository_container: RepositoryContainer,
+    ) -> Self {
+        let app_config = Arc::new(app_config);
+        let repository_container = Arc::new(repository_container);
+        let metrics = Arc::new(Metrics::new());
+        let service_container = Arc::new(ServiceContainer::new(
+            db_service,
+            repository_container.clone(),
+            app_config.clone(),
+            metrics.clone(),
+        ));
         Self {
-            app_config: Arc::new(app_config),
-            repository_container: Arc::new(repository_container),
+            app_config,
+            repository_container,
+            service_container,
+            metrics,
         }
     }
 }
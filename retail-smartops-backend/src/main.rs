@@ -1,6 +1,30 @@
-use retail_smartops_backend::run_app;
+use retail_smartops_backend::{
+    print_config, run_app, run_bootstrap_admin_command, run_migrate_command,
+};
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<(), std::io::Error> {
-    run_app(None).await
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("migrate") => run_migrate_command(args.get(1).map_or("status", String::as_str)).await,
+        Some("bootstrap-admin") => {
+            run_bootstrap_admin_command(
+                args.get(1).map(String::as_str),
+                args.get(2).map(String::as_str),
+            )
+            .await
+        }
+        _ if args.iter().any(|arg| arg == "--print-config") => print_config(),
+        _ => run_app(None).await,
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -0,0 +1,213 @@
+use crate::AppState;
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use std::time::Instant;
+
+/// The path label of requests that matched no route, so unknown URLs cannot blow up the
+/// number of series.
+const UNMATCHED_PATH: &str = "unmatched";
+
+/// A business event counted by the metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomainEvent {
+    Login,
+    FailedLogin,
+}
+
+impl DomainEvent {
+    const ALL: [DomainEvent; 2] = [DomainEvent::Login, DomainEvent::FailedLogin];
+
+    fn metric_name(&self) -> &'static str {
+        match self {
+            DomainEvent::Login => "auth_logins_total",
+            DomainEvent::FailedLogin => "auth_login_failures_total",
+        }
+    }
+
+    fn help(&self) -> &'static str {
+        match self {
+            DomainEvent::Login => "Number of successful logins.",
+            DomainEvent::FailedLogin => "Number of rejected login attempts.",
+        }
+    }
+}
+
+/// The Prometheus metrics of the application.
+///
+/// HTTP metrics are recorded by the `track_http_metrics` middleware, domain events by the
+/// services through `record`. Pool gauges are refreshed right before every scrape.
+pub struct Metrics {
+    registry: Registry,
+    http_requests_total: IntCounterVec,
+    http_request_duration_seconds: HistogramVec,
+    db_pool_connections: IntGaugeVec,
+    db_pool_max_connections: IntGauge,
+    domain_events: Vec<(DomainEvent, IntCounter)>,
+}
+
+impl Metrics {
+    /// Creates and registers every metric.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if two metrics share a name, which is a programming error.
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Number of HTTP requests handled."),
+            &["method", "path", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time spent handling HTTP requests.",
+            ),
+            &["method", "path", "status"],
+        )
+        .expect("valid metric");
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new(
+                "db_pool_connections",
+                "Number of open database connections by state.",
+            ),
+            &["state"],
+        )
+        .expect("valid metric");
+        let db_pool_max_connections = IntGauge::new(
+            "db_pool_max_connections",
+            "Largest number of connections the database pool opens.",
+        )
+        .expect("valid metric");
+
+        registry
+            .register(Box::new(http_requests_total.clone()))
+            .expect("unique metric");
+        registry
+            .register(Box::new(http_request_duration_seconds.clone()))
+            .expect("unique metric");
+        registry
+            .register(Box::new(db_pool_connections.clone()))
+            .expect("unique metric");
+        registry
+            .register(Box::new(db_pool_max_connections.clone()))
+            .expect("unique metric");
+
+        let domain_events = DomainEvent::ALL
+            .iter()
+            .map(|event| {
+                let counter =
+                    IntCounter::new(event.metric_name(), event.help()).expect("valid metric");
+                registry
+                    .register(Box::new(counter.clone()))
+                    .expect("unique metric");
+                (*event, counter)
+            })
+            .collect();
+
+        Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            db_pool_connections,
+            db_pool_max_connections,
+            domain_events,
+        }
+    }
+
+    /// Counts a domain event.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event that happened.
+    pub fn record(&self, event: DomainEvent) {
+        if let Some((_, counter)) = self.domain_events.iter().find(|(e, _)| *e == event) {
+            counter.inc();
+        }
+    }
+
+    /// Records a handled HTTP request.
+    fn observe_request(&self, method: &str, path: &str, status: &str, seconds: f64) {
+        let labels = [method, path, status];
+        self.http_requests_total.with_label_values(&labels).inc();
+        self.http_request_duration_seconds
+            .with_label_values(&labels)
+            .observe(seconds);
+    }
+
+    /// Updates the pool gauges.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of open connections.
+    /// * `idle` - The number of open connections not in use.
+    /// * `max` - The largest number of connections the pool opens.
+    pub fn set_pool_usage(&self, size: u32, idle: usize, max: u32) {
+        let idle = idle as i64;
+        self.db_pool_connections
+            .with_label_values(&["idle"])
+            .set(idle);
+        self.db_pool_connections
+            .with_label_values(&["in_use"])
+            .set((i64::from(size) - idle).max(0));
+        self.db_pool_max_connections.set(i64::from(max));
+    }
+
+    /// Renders every metric in the Prometheus text exposition format.
+    ///
+    /// # Returns
+    ///
+    /// A `String` with the encoded metrics.
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("metrics are always encodable");
+        String::from_utf8(buffer).expect("metrics are valid UTF-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Middleware that records the count and latency of every request.
+///
+/// Requests are labelled with their method, the route they matched (e.g. `/api/users/:id`,
+/// never the raw URL) and the status code of the response.
+///
+/// # Returns
+///
+/// The `Response` of the inner service.
+pub async fn track_http_metrics(
+    State(app_state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started_at = Instant::now();
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED_PATH, MatchedPath::as_str)
+        .to_string();
+
+    let response = next.run(request).await;
+
+    app_state.metrics.observe_request(
+        &method,
+        &path,
+        response.status().as_str(),
+        started_at.elapsed().as_secs_f64(),
+    );
+
+    response
+}
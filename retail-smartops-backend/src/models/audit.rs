@@ -0,0 +1,158 @@
+use crate::models::list::ListQuery;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// The kind of change recorded by an audit event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Purge,
+    Deactivate,
+    Reactivate,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::Purge => "purge",
+            AuditAction::Deactivate => "deactivate",
+            AuditAction::Reactivate => "reactivate",
+        }
+    }
+}
+
+/// The kind of record an audit event is about.
+///
+/// Role assignments are recorded as `UserRole` events identified by the user holding them,
+/// permission grants as `RolePermission` events identified by the role, store memberships as
+/// `StoreUser` events identified by `store_id:user_id`, and lifted login lockouts as
+/// `LoginLockout` events identified by their scope and key, e.g. `username:alice`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEntity {
+    User,
+    Role,
+    UserRole,
+    Permission,
+    RolePermission,
+    Store,
+    StoreUser,
+    LoginLockout,
+}
+
+impl AuditEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEntity::User => "user",
+            AuditEntity::Role => "role",
+            AuditEntity::UserRole => "user_role",
+            AuditEntity::Permission => "permission",
+            AuditEntity::RolePermission => "role_permission",
+            AuditEntity::Store => "store",
+            AuditEntity::StoreUser => "store_user",
+            AuditEntity::LoginLockout => "login_lockout",
+        }
+    }
+}
+
+/// Data Transfer Object for recording an audit event.
+///
+/// # Fields
+///
+/// * `actor_id` - The user who made the change, or `None` if the caller is not authenticated.
+/// * `action` - The kind of change.
+/// * `entity` - The kind of record that changed.
+/// * `entity_id` - The identifier of the record that changed.
+/// * `before` - The changed fields before the change, or `None` if the record was created.
+/// * `after` - The changed fields after the change, or `None` if the record was removed.
+/// * `ip_address` - The IP address the request came from.
+/// * `request_id` - The ID of the request that made the change.
+#[derive(Debug)]
+pub struct CreateAuditEventDTO {
+    pub actor_id: Option<Uuid>,
+    pub action: AuditAction,
+    pub entity: AuditEntity,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// Query parameters of the audit log.
+///
+/// Pagination and sorting behave as for the other list endpoints.
+///
+/// # Fields
+///
+/// * `limit` - The maximum number of events to return, between 1 and 200. Defaults to 50.
+/// * `cursor` - The cursor of the page to return, or `None` for the first page.
+/// * `sort` - `occurred_at` or `-occurred_at`. Defaults to newest first.
+/// * `actor_id` - Only return events of changes made by this user.
+/// * `action` - Only return events of this kind of change, e.g. `update`.
+/// * `entity` - Only return events about this kind of record, e.g. `role`.
+/// * `entity_id` - Only return events about the record with this identifier.
+/// * `since` - Only return events that occurred at or after this timestamp.
+/// * `until` - Only return events that occurred before this timestamp.
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub sort: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub entity: Option<String>,
+    pub entity_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditQuery {
+    /// Returns the pagination and sorting part of the query.
+    pub fn page(&self) -> ListQuery {
+        ListQuery {
+            limit: self.limit,
+            cursor: self.cursor.clone(),
+            sort: self.sort.clone(),
+            ..ListQuery::default()
+        }
+    }
+}
+
+/// Data Transfer Object for responding with an audit event.
+///
+/// # Fields
+///
+/// * `id` - The identifier of the event.
+/// * `occurred_at` - The timestamp of the change.
+/// * `actor_id` - The user who made the change, or `None` if the caller was not authenticated.
+/// * `action` - The kind of change, e.g. `update`.
+/// * `entity` - The kind of record that changed, e.g. `role`.
+/// * `entity_id` - The identifier of the record that changed.
+/// * `before` - The changed fields before the change.
+/// * `after` - The changed fields after the change.
+/// * `ip_address` - The IP address the request came from.
+/// * `request_id` - The ID of the request that made the change.
+#[derive(Debug, Serialize)]
+pub struct AuditEventDTO {
+    pub id: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub entity: String,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+}
@@ -0,0 +1,138 @@
+use crate::validation::validate_not_blank;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use validator::{Validate, ValidationError};
+
+/// Data Transfer Object for logging in.
+///
+/// # Fields
+///
+/// * `username` - The username of the user.
+/// * `password` - The password of the user.
+#[derive(Debug, Deserialize, Validate)]
+pub struct LoginDTO {
+    #[validate(length(min = 1, max = 50, message = "must be between 1 and 50 characters"))]
+    pub username: String,
+    #[validate(length(min = 1, max = 128, message = "must be between 1 and 128 characters"))]
+    pub password: String,
+}
+
+/// Data Transfer Object carrying a refresh token.
+///
+/// This struct is used to deserialize the payload when refreshing a session or logging out.
+///
+/// # Fields
+///
+/// * `refresh_token` - The refresh token issued at login.
+#[derive(Debug, Deserialize, Validate)]
+pub struct RefreshTokenDTO {
+    #[validate(custom(function = validate_not_blank))]
+    pub refresh_token: String,
+}
+
+/// Data Transfer Object for responding with issued tokens.
+///
+/// # Fields
+///
+/// * `access_token` - The signed access token.
+/// * `refresh_token` - The opaque refresh token.
+/// * `token_type` - The type of the access token, always `Bearer`.
+/// * `expires_in` - The lifetime of the access token in seconds.
+#[derive(Debug, Serialize)]
+pub struct TokenResponseDTO {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+}
+
+/// What failed logins are counted against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginThrottleScope {
+    /// The submitted username, whether or not a user has it.
+    Username,
+    /// The IP address the login came from.
+    Ip,
+}
+
+impl LoginThrottleScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginThrottleScope::Username => "username",
+            LoginThrottleScope::Ip => "ip",
+        }
+    }
+}
+
+/// When failed logins lock a key out and for how long.
+///
+/// # Fields
+///
+/// * `max_attempts` - The number of failed logins that locks the key out.
+/// * `attempt_window` - How long a failed login is remembered after the last one or the end of
+///   the last lockout, in seconds.
+/// * `lockout_base` - The duration of the first lockout, in seconds.
+/// * `lockout_max` - The longest lockout, in seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct LoginThrottlePolicy {
+    pub max_attempts: u32,
+    pub attempt_window: u64,
+    pub lockout_base: u64,
+    pub lockout_max: u64,
+}
+
+/// Data Transfer Object for the outcome of counting a login attempt.
+///
+/// # Fields
+///
+/// * `failed_attempts` - The number of failed logins in the window, this one included.
+/// * `locked_until` - The end of the lockout of the key, if any.
+/// * `counted` - Whether the attempt was counted. Attempts made while the key is locked out
+///   are not counted and must be rejected.
+#[derive(Debug, Clone, Copy)]
+pub struct LoginAttemptDTO {
+    pub failed_attempts: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub counted: bool,
+}
+
+/// Data Transfer Object for the failed logins counted against a key.
+///
+/// # Fields
+///
+/// * `failed_attempts` - The number of failed logins in the window.
+/// * `last_failed_at` - The timestamp of the last failed login.
+/// * `locked_until` - The end of the lockout of the key, if any.
+#[derive(Debug, Serialize)]
+pub struct LoginThrottleDTO {
+    pub failed_attempts: i32,
+    pub last_failed_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+/// Data Transfer Object for lifting a login lockout.
+///
+/// # Fields
+///
+/// * `username` - The username to unlock.
+/// * `ip_address` - The IP address to unlock.
+///
+/// At least one of them is required. When both are given, both are unlocked.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = validate_unlock_target))]
+pub struct UnlockLoginDTO {
+    #[validate(length(min = 1, max = 50, message = "must be between 1 and 50 characters"))]
+    pub username: Option<String>,
+    pub ip_address: Option<IpAddr>,
+}
+
+/// Checks that an unlock names a username or an IP address.
+fn validate_unlock_target(payload: &UnlockLoginDTO) -> Result<(), ValidationError> {
+    if payload.username.is_none() && payload.ip_address.is_none() {
+        return Err(ValidationError::new("username")
+            .with_message("username or ip_address is required".into()));
+    }
+
+    Ok(())
+}
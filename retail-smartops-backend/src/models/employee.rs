@@ -0,0 +1,70 @@
+use crate::entities::user::User;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Data Transfer Object for responding with employee information.
+///
+/// Unlike `UserResponseDTO`, it exposes the employment status of the user.
+///
+/// # Fields
+///
+/// * `id` - The unique identifier of the employee.
+/// * `username` - The username of the employee.
+/// * `email` - The email address of the employee.
+/// * `is_active` - Indicates if the employee is active.
+/// * `created_at` - The timestamp when the employee was created.
+/// * `updated_at` - The timestamp when the employee was last updated.
+#[derive(Debug, Serialize)]
+pub struct EmployeeResponseDTO {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for EmployeeResponseDTO {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            is_active: user.is_active,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+/// The kind of transition an employee went through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmployeeLifecycleEventKind {
+    Deactivated,
+    Reactivated,
+}
+
+/// Data Transfer Object describing an employee lifecycle transition and its side effects.
+///
+/// # Fields
+///
+/// * `employee_id` - The unique identifier of the employee.
+/// * `event` - The transition the employee went through.
+/// * `occurred_at` - The timestamp of the transition.
+/// * `revoked_sessions` - The number of sessions revoked by the transition.
+/// * `removed_store_memberships` - The number of stores the employee was removed from.
+/// * `reassigned_reports` - The direct reports moved to `new_manager_id`, or detached from the
+///   hierarchy when it is `None`.
+/// * `new_manager_id` - The manager the direct reports now report to.
+#[derive(Debug, Serialize)]
+pub struct EmployeeLifecycleEventDTO {
+    pub employee_id: Uuid,
+    pub event: EmployeeLifecycleEventKind,
+    pub occurred_at: DateTime<Utc>,
+    pub revoked_sessions: u64,
+    pub removed_store_memberships: u64,
+    pub reassigned_reports: Vec<Uuid>,
+    pub new_manager_id: Option<Uuid>,
+}
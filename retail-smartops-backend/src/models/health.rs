@@ -0,0 +1,67 @@
+use serde::Serialize;
+
+/// Data Transfer Object for responding with the readiness of the service.
+///
+/// # Fields
+///
+/// * `status` - `ready` when the service can handle requests.
+/// * `database` - The state of the database connection pool.
+/// * `migrations` - The schema version of the database and of this build.
+/// * `build` - The name and version of this build.
+#[derive(Debug, Serialize)]
+pub struct ReadinessDTO {
+    pub status: &'static str,
+    pub database: DatabaseHealthDTO,
+    pub migrations: MigrationHealthDTO,
+    pub build: BuildInfoDTO,
+}
+
+/// Data Transfer Object for the state of the database connection pool.
+///
+/// # Fields
+///
+/// * `latency_ms` - The round-trip time of the readiness query in milliseconds.
+/// * `pool_size` - The number of open connections.
+/// * `idle_connections` - The number of open connections not in use.
+/// * `max_connections` - The largest number of connections the pool opens.
+#[derive(Debug, Serialize)]
+pub struct DatabaseHealthDTO {
+    pub latency_ms: u128,
+    pub pool_size: u32,
+    pub idle_connections: usize,
+    pub max_connections: u32,
+}
+
+/// Data Transfer Object for the schema version.
+///
+/// # Fields
+///
+/// * `applied_version` - The latest migration applied to the database.
+/// * `expected_version` - The latest migration embedded in this build.
+#[derive(Debug, Serialize)]
+pub struct MigrationHealthDTO {
+    pub applied_version: Option<i64>,
+    pub expected_version: Option<i64>,
+}
+
+/// Data Transfer Object for the build of the service.
+///
+/// # Fields
+///
+/// * `name` - The name of the package.
+/// * `version` - The version of the package.
+#[derive(Debug, Serialize)]
+pub struct BuildInfoDTO {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+impl BuildInfoDTO {
+    /// Returns the build information of the running binary.
+    pub fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use crate::errors::{AppError, ErrorDetail};
+use serde::{Deserialize, Serialize};
+
+/// The number of items returned when a list query has no `limit`.
+const DEFAULT_LIMIT: i64 = 50;
+
+/// The largest page a list query can request.
+const MAX_LIMIT: i64 = 200;
+
+/// Query parameters shared by the list endpoints.
+///
+/// Pagination uses an opaque `cursor` taken from the `next_cursor` of the previous page.
+/// `sort` names a field, prefixed with `-` for descending order. Filters that do not apply
+/// to the listed entity are ignored.
+///
+/// # Fields
+///
+/// * `limit` - The maximum number of items to return, between 1 and 200. Defaults to 50.
+/// * `cursor` - The cursor of the page to return, or `None` for the first page.
+/// * `sort` - The field to sort by, e.g. `username` or `-created_at`.
+/// * `is_active` - Only return users with this activity status.
+/// * `email` - Only return users whose email contains this value, ignoring case.
+/// * `role` - Only return users holding the role with this name, in any scope.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub sort: Option<String>,
+    pub is_active: Option<bool>,
+    pub email: Option<String>,
+    pub role: Option<String>,
+}
+
+impl ListQuery {
+    /// Returns the page size, clamped to the allowed range.
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    /// Decodes the cursor into the number of items to skip.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<i64, AppError>` - The offset of the page, or `AppError::BadRequest` if the cursor is invalid.
+    pub fn offset(&self) -> Result<i64, AppError> {
+        match &self.cursor {
+            Some(cursor) => match cursor.parse::<i64>() {
+                Ok(offset) if offset >= 0 => Ok(offset),
+                _ => Err(AppError::bad_request(
+                    "invalid_cursor",
+                    "The cursor is not a valid page cursor",
+                )),
+            },
+            None => Ok(0),
+        }
+    }
+
+    /// Resolves the sort parameter against the fields an entity can be sorted by.
+    ///
+    /// # Arguments
+    ///
+    /// * `allowed` - The sortable fields of the entity.
+    /// * `default` - The sort key used when the query has none.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, AppError>` - The sort key, or `AppError::BadRequest` if the field is not sortable.
+    pub fn sort_key(&self, allowed: &[&str], default: &str) -> Result<String, AppError> {
+        let sort = match &self.sort {
+            Some(sort) => sort.as_str(),
+            None => return Ok(default.to_string()),
+        };
+
+        if !allowed.contains(&sort.trim_start_matches('-')) {
+            return Err(AppError::BadRequest(
+                ErrorDetail::new("invalid_sort", "The list cannot be sorted by this field")
+                    .with_field("sort", format!("must be one of: {}", allowed.join(", "))),
+            ));
+        }
+
+        Ok(sort.to_string())
+    }
+
+    /// Builds a case-insensitive `LIKE` pattern matching values that contain the email filter.
+    pub fn email_pattern(&self) -> Option<String> {
+        self.email.as_deref().map(|email| {
+            let escaped = email
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            format!("%{escaped}%")
+        })
+    }
+}
+
+/// A page of a list endpoint.
+///
+/// # Fields
+///
+/// * `items` - The items of the page.
+/// * `next_cursor` - The cursor of the next page, or `None` on the last page.
+/// * `total` - The number of items matching the query across every page.
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub total: i64,
+}
+
+impl<T> Page<T> {
+    /// Creates a page and derives the cursor of the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The items of the page.
+    /// * `total` - The number of items matching the query across every page.
+    /// * `offset` - The number of items skipped before this page.
+    pub fn new(items: Vec<T>, total: i64, offset: i64) -> Self {
+        let next_offset = offset + items.len() as i64;
+        let next_cursor =
+            (!items.is_empty() && next_offset < total).then(|| next_offset.to_string());
+
+        Self {
+            items,
+            next_cursor,
+            total,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(limit: Option<i64>, cursor: Option<&str>, sort: Option<&str>) -> ListQuery {
+        ListQuery {
+            limit,
+            cursor: cursor.map(str::to_string),
+            sort: sort.map(str::to_string),
+            ..ListQuery::default()
+        }
+    }
+
+    #[test]
+    fn clamps_limit() {
+        assert_eq!(query(None, None, None).limit(), DEFAULT_LIMIT);
+        assert_eq!(query(Some(0), None, None).limit(), 1);
+        assert_eq!(query(Some(-5), None, None).limit(), 1);
+        assert_eq!(query(Some(20), None, None).limit(), 20);
+        assert_eq!(query(Some(10_000), None, None).limit(), MAX_LIMIT);
+    }
+
+    #[test]
+    fn decodes_cursor() {
+        assert_eq!(query(None, None, None).offset().unwrap(), 0);
+        assert_eq!(query(None, Some("150"), None).offset().unwrap(), 150);
+    }
+
+    #[test]
+    fn rejects_invalid_cursor() {
+        for cursor in ["-1", "abc", ""] {
+            assert!(matches!(
+                query(None, Some(cursor), None).offset(),
+                Err(AppError::BadRequest(detail)) if detail.code == "invalid_cursor"
+            ));
+        }
+    }
+
+    #[test]
+    fn resolves_sort_key() {
+        let allowed = ["id", "name"];
+
+        assert_eq!(
+            query(None, None, None).sort_key(&allowed, "id").unwrap(),
+            "id"
+        );
+        assert_eq!(
+            query(None, None, Some("-name"))
+                .sort_key(&allowed, "id")
+                .unwrap(),
+            "-name"
+        );
+        assert!(matches!(
+            query(None, None, Some("password_hash")).sort_key(&allowed, "id"),
+            Err(AppError::BadRequest(detail)) if detail.code == "invalid_sort"
+        ));
+    }
+
+    #[test]
+    fn escapes_email_pattern() {
+        let query = ListQuery {
+            email: Some("a_b%c\\d".to_string()),
+            ..ListQuery::default()
+        };
+
+        assert_eq!(query.email_pattern().as_deref(), Some("%a\\_b\\%c\\\\d%"));
+    }
+
+    #[test]
+    fn derives_next_cursor() {
+        let first = Page::new(vec![1, 2], 5, 0);
+        let last = Page::new(vec![5], 5, 4);
+        let past_end = Page::<i32>::new(vec![], 5, 10);
+
+        assert_eq!(first.next_cursor.as_deref(), Some("2"));
+        assert_eq!(first.total, 5);
+        assert_eq!(last.next_cursor, None);
+        assert_eq!(past_end.next_cursor, None);
+    }
+}
@@ -1,3 +1,13 @@
+pub mod audit;
+pub mod auth;
+pub mod employee;
+pub mod health;
+pub mod list;
+pub mod permission;
 pub mod role;
+pub mod role_permission;
+pub mod store;
+pub mod store_users;
 pub mod user;
+pub mod user_hierarchy;
 pub mod user_role;
@@ -0,0 +1,81 @@
+use crate::entities::permission::Permission;
+use crate::validation::validate_not_blank;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Data Transfer Object for creating a new permission.
+///
+/// # Fields
+///
+/// * `entity_name` - The name of the entity the permission applies to.
+/// * `can_read` - Whether the permission allows reading. Defaults to `true`.
+/// * `can_write` - Whether the permission allows writing. Defaults to `false`.
+/// * `can_delete` - Whether the permission allows deleting. Defaults to `false`.
+/// * `can_update` - Whether the permission allows updating. Defaults to `false`.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreatePermissionDTO {
+    #[validate(
+        length(min = 1, max = 100, message = "must be between 1 and 100 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub entity_name: String,
+    pub can_read: Option<bool>,
+    pub can_write: Option<bool>,
+    pub can_delete: Option<bool>,
+    pub can_update: Option<bool>,
+}
+
+/// Data Transfer Object for updating an existing permission.
+///
+/// # Fields
+///
+/// * `entity_name` - An optional new entity name for the permission.
+/// * `can_read` - An optional new value for the read flag.
+/// * `can_write` - An optional new value for the write flag.
+/// * `can_delete` - An optional new value for the delete flag.
+/// * `can_update` - An optional new value for the update flag.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdatePermissionDTO {
+    #[validate(
+        length(min = 1, max = 100, message = "must be between 1 and 100 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub entity_name: Option<String>,
+    pub can_read: Option<bool>,
+    pub can_write: Option<bool>,
+    pub can_delete: Option<bool>,
+    pub can_update: Option<bool>,
+}
+
+/// Data Transfer Object for responding with permission details.
+///
+/// # Fields
+///
+/// * `id` - The unique identifier of the permission.
+/// * `entity_name` - The name of the entity the permission applies to.
+/// * `can_read` - Whether the permission allows reading.
+/// * `can_write` - Whether the permission allows writing.
+/// * `can_delete` - Whether the permission allows deleting.
+/// * `can_update` - Whether the permission allows updating.
+#[derive(Debug, Serialize)]
+pub struct PermissionResponseDTO {
+    pub id: i32,
+    pub entity_name: String,
+    pub can_read: bool,
+    pub can_write: bool,
+    pub can_delete: bool,
+    pub can_update: bool,
+}
+
+impl From<Permission> for PermissionResponseDTO {
+    fn from(permission: Permission) -> Self {
+        Self {
+            id: permission.id,
+            entity_name: permission.entity_name,
+            can_read: permission.can_read,
+            can_write: permission.can_write,
+            can_delete: permission.can_delete,
+            can_update: permission.can_update,
+        }
+    }
+}
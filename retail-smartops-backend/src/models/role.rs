@@ -1,4 +1,7 @@
+use crate::entities::role::Role;
+use crate::validation::validate_not_blank;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /// Data Transfer Object for creating a new role.
 ///
@@ -7,8 +10,12 @@ use serde::{Deserialize, Serialize};
 /// # Fields
 ///
 /// * `name` - The name of the role.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateRoleDTO {
+    #[validate(
+        length(min = 1, max = 30, message = "must be between 1 and 30 characters"),
+        custom(function = validate_not_blank)
+    )]
     pub name: String,
 }
 
@@ -19,8 +26,12 @@ pub struct CreateRoleDTO {
 /// # Fields
 ///
 /// * `name` - The new name of the role. This field is optional.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdateRoleDTO {
+    #[validate(
+        length(min = 1, max = 30, message = "must be between 1 and 30 characters"),
+        custom(function = validate_not_blank)
+    )]
     pub name: Option<String>,
 }
 
@@ -37,3 +48,12 @@ pub struct RoleResponseDTO {
     pub id: i32,
     pub name: String,
 }
+
+impl From<Role> for RoleResponseDTO {
+    fn from(role: Role) -> Self {
+        Self {
+            id: role.id,
+            name: role.name,
+        }
+    }
+}
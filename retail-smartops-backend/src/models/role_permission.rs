@@ -0,0 +1,23 @@
+use crate::entities::role_permission::RolePermission;
+use serde::Serialize;
+
+/// Data Transfer Object for responding with role permission information.
+///
+/// # Fields
+///
+/// * `role_id` - The identifier of the role.
+/// * `permission_id` - The identifier of the permission assigned to the role.
+#[derive(Debug, Serialize)]
+pub struct RolePermissionResponseDTO {
+    pub role_id: i32,
+    pub permission_id: i32,
+}
+
+impl From<RolePermission> for RolePermissionResponseDTO {
+    fn from(role_permission: RolePermission) -> Self {
+        Self {
+            role_id: role_permission.role_id,
+            permission_id: role_permission.permission_id,
+        }
+    }
+}
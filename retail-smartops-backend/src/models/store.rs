@@ -0,0 +1,166 @@
+use crate::entities::store::{Address, Store};
+use crate::validation::validate_not_blank;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Data Transfer Object for a store address.
+///
+/// # Fields
+///
+/// * `country` - The country of the store.
+/// * `state` - The state of the store.
+/// * `city` - The city of the store.
+/// * `street` - The street of the store.
+/// * `zip` - The zip code of the store.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AddressDTO {
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub country: String,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub state: String,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub city: String,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub street: String,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub zip: String,
+}
+
+impl From<Address> for AddressDTO {
+    fn from(address: Address) -> Self {
+        Self {
+            country: address.country,
+            state: address.state,
+            city: address.city,
+            street: address.street,
+            zip: address.zip,
+        }
+    }
+}
+
+/// Data Transfer Object for creating a new store.
+///
+/// # Fields
+///
+/// * `name` - The name of the new store.
+/// * `owner_id` - The unique identifier of the user owning the store.
+/// * `address` - The address of the new store.
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateStoreDTO {
+    #[validate(
+        length(min = 1, max = 255, message = "must be between 1 and 255 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub name: String,
+    pub owner_id: Uuid,
+    #[validate(nested)]
+    pub address: AddressDTO,
+}
+
+/// Data Transfer Object for updating a store address. Missing fields are left unchanged.
+///
+/// # Fields
+///
+/// * `country` - An optional new country.
+/// * `state` - An optional new state.
+/// * `city` - An optional new city.
+/// * `street` - An optional new street.
+/// * `zip` - An optional new zip code.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct UpdateAddressDTO {
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub country: Option<String>,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub state: Option<String>,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub city: Option<String>,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub street: Option<String>,
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub zip: Option<String>,
+}
+
+/// Data Transfer Object for updating an existing store.
+///
+/// # Fields
+///
+/// * `name` - An optional new name for the store.
+/// * `owner_id` - An optional new owner for the store.
+/// * `address` - Optional changes to the address of the store.
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateStoreDTO {
+    #[validate(
+        length(min = 1, max = 255, message = "must be between 1 and 255 characters"),
+        custom(function = validate_not_blank)
+    )]
+    pub name: Option<String>,
+    pub owner_id: Option<Uuid>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub address: UpdateAddressDTO,
+}
+
+/// Data Transfer Object for responding with store information.
+///
+/// # Fields
+///
+/// * `id` - The unique identifier of the store.
+/// * `owner_id` - The unique identifier of the user owning the store.
+/// * `name` - The name of the store.
+/// * `address` - The address of the store.
+/// * `created_at` - The timestamp when the store was created.
+/// * `updated_at` - The timestamp when the store was last updated.
+#[derive(Debug, Serialize)]
+pub struct StoreResponseDTO {
+    pub id: i32,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub address: AddressDTO,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Store> for StoreResponseDTO {
+    fn from(store: Store) -> Self {
+        Self {
+            id: store.id,
+            owner_id: store.owner_id,
+            name: store.name,
+            address: store.address.into(),
+            created_at: store.created_at,
+            updated_at: store.updated_at,
+        }
+    }
+}
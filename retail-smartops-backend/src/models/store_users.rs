@@ -0,0 +1,68 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// Data Transfer Object for adding a user to a store.
+///
+/// # Fields
+///
+/// * `user_id` - The unique identifier of the user joining the store.
+/// * `starts_on` - The first day of the membership, or `None` to start immediately.
+/// * `ends_on` - The last day of the membership, or `None` for an open-ended membership.
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = validate_period))]
+pub struct AddStoreUserDTO {
+    pub user_id: Uuid,
+    pub starts_on: Option<NaiveDate>,
+    pub ends_on: Option<NaiveDate>,
+}
+
+/// Checks that a membership period does not end before it starts.
+fn validate_period(payload: &AddStoreUserDTO) -> Result<(), ValidationError> {
+    if let (Some(starts_on), Some(ends_on)) = (payload.starts_on, payload.ends_on) {
+        if ends_on < starts_on {
+            return Err(
+                ValidationError::new("ends_on").with_message("must not be before starts_on".into())
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Data Transfer Object for responding with a store member and the roles they hold there.
+///
+/// # Fields
+///
+/// * `user_id` - The unique identifier of the user.
+/// * `username` - The username of the user.
+/// * `email` - The email address of the user.
+/// * `starts_on` - The first day of the membership, if bounded.
+/// * `ends_on` - The last day of the membership, if bounded.
+/// * `roles` - The names of the roles the user holds in the store, including global roles.
+#[derive(Debug, Serialize)]
+pub struct StoreStaffDTO {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub starts_on: Option<NaiveDate>,
+    pub ends_on: Option<NaiveDate>,
+    pub roles: Vec<String>,
+}
+
+/// Data Transfer Object for responding with a store a user works at.
+///
+/// # Fields
+///
+/// * `store_id` - The unique identifier of the store.
+/// * `store_name` - The name of the store.
+/// * `starts_on` - The first day of the membership, if bounded.
+/// * `ends_on` - The last day of the membership, if bounded.
+#[derive(Debug, Serialize)]
+pub struct UserStoreDTO {
+    pub store_id: i32,
+    pub store_name: String,
+    pub starts_on: Option<NaiveDate>,
+    pub ends_on: Option<NaiveDate>,
+}
@@ -1,5 +1,8 @@
+use crate::entities::user::User;
+use crate::validation::{validate_password, validate_username};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use validator::Validate;
 
 /// Data Transfer Object for creating a new user.
 ///
@@ -8,10 +11,19 @@ use uuid::Uuid;
 /// * `username` - The username of the new user.
 /// * `email` - The email address of the new user.
 /// * `password` - The password for the new user.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateUserDTO {
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_username)
+    )]
     pub username: String,
+    #[validate(
+        email(message = "must be a valid email address"),
+        length(max = 50, message = "must be at most 50 characters")
+    )]
     pub email: String,
+    #[validate(custom(function = validate_password))]
     pub password: String,
 }
 
@@ -22,13 +34,33 @@ pub struct CreateUserDTO {
 /// * `username` - An optional new username for the user.
 /// * `email` - An optional new email address for the user.
 /// * `password` - An optional new password for the user.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdateUserDTO {
+    #[validate(
+        length(min = 1, max = 50, message = "must be between 1 and 50 characters"),
+        custom(function = validate_username)
+    )]
     pub username: Option<String>,
+    #[validate(
+        email(message = "must be a valid email address"),
+        length(max = 50, message = "must be at most 50 characters")
+    )]
     pub email: Option<String>,
+    #[validate(custom(function = validate_password))]
     pub password: Option<String>,
 }
 
+/// Query parameters for permanently removing a user.
+///
+/// # Fields
+///
+/// * `new_owner_id` - The user taking over the stores owned by the purged user. Required when
+///   the purged user owns stores.
+#[derive(Debug, Deserialize)]
+pub struct PurgeUserQuery {
+    pub new_owner_id: Option<Uuid>,
+}
+
 /// Data Transfer Object for responding with user information.
 ///
 /// # Fields
@@ -42,3 +74,13 @@ pub struct UserResponseDTO {
     pub username: String,
     pub email: String,
 }
+
+impl From<User> for UserResponseDTO {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+        }
+    }
+}
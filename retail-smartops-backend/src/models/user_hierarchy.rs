@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Data Transfer Object for assigning a manager to a user.
+///
+/// # Fields
+///
+/// * `manager_id` - The unique identifier of the user to report to.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetManagerDTO {
+    pub manager_id: Uuid,
+}
+
+/// Data Transfer Object for responding with a user's position in the org chart.
+///
+/// # Fields
+///
+/// * `id` - The unique identifier of the user.
+/// * `username` - The username of the user.
+/// * `email` - The email address of the user.
+/// * `reports_to` - The unique identifier of the user's manager, or `None` at the top of the chart.
+/// * `depth` - The number of reporting levels between this user and the user the query started from.
+#[derive(Debug, Serialize)]
+pub struct OrgChartEntryDTO {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub reports_to: Option<Uuid>,
+    pub depth: i32,
+}
@@ -1,5 +1,53 @@
-use serde::Serialize;
+use crate::entities::user_role::UserRole;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use validator::Validate;
+
+/// Data Transfer Object for assigning a role to a user.
+///
+/// # Fields
+///
+/// * `role_id` - The identifier of the role to assign.
+/// * `store_id` - The identifier of the store the role applies to, or `None` for a global assignment.
+#[derive(Debug, Deserialize, Validate)]
+pub struct AssignUserRoleDTO {
+    pub role_id: i32,
+    pub store_id: Option<i32>,
+}
+
+/// Data Transfer Object for replacing one of a user's roles with another.
+///
+/// # Fields
+///
+/// * `new_role_id` - The identifier of the role replacing the current one.
+/// * `store_id` - The identifier of the store the role applies to, or `None` for a global assignment.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReplaceUserRoleDTO {
+    pub new_role_id: i32,
+    pub store_id: Option<i32>,
+}
+
+/// Data Transfer Object for setting the complete role set of a user.
+///
+/// # Fields
+///
+/// * `roles` - The role assignments the user should hold. Any other assignment is removed.
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetUserRolesDTO {
+    #[validate(nested)]
+    pub roles: Vec<AssignUserRoleDTO>,
+}
+
+/// Query parameters selecting the scope of a role assignment.
+///
+/// # Fields
+///
+/// * `store_id` - The identifier of the store the role applies to, or `None` for a global assignment.
+#[derive(Debug, Deserialize)]
+pub struct UserRoleScopeQuery {
+    pub store_id: Option<i32>,
+}
 
 /// Data Transfer Object for responding with user role information.
 ///
@@ -7,8 +55,54 @@ use uuid::Uuid;
 ///
 /// * `user_id` - The unique identifier of the user.
 /// * `role_id` - The identifier of the role assigned to the user.
+/// * `store_id` - The identifier of the store the role applies to, or `None` for a global assignment.
 #[derive(Debug, Serialize)]
 pub struct UserRoleResponseDTO {
     pub user_id: Uuid,
     pub role_id: i32,
+    pub store_id: Option<i32>,
+}
+
+impl From<UserRole> for UserRoleResponseDTO {
+    fn from(user_role: UserRole) -> Self {
+        Self {
+            user_id: user_role.user_id,
+            role_id: user_role.role_id,
+            store_id: user_role.store_id,
+        }
+    }
+}
+
+/// Data Transfer Object for responding with a role held by a user.
+///
+/// # Fields
+///
+/// * `role_id` - The identifier of the role.
+/// * `role_name` - The name of the role.
+/// * `store_id` - The identifier of the store the role applies to, or `None` for a global assignment.
+/// * `assigned_at` - The timestamp when the role was assigned.
+#[derive(Debug, Serialize)]
+pub struct UserRoleDetailsDTO {
+    pub role_id: i32,
+    pub role_name: String,
+    pub store_id: Option<i32>,
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// Data Transfer Object for responding with a user holding a role.
+///
+/// # Fields
+///
+/// * `user_id` - The unique identifier of the user.
+/// * `username` - The username of the user.
+/// * `email` - The email address of the user.
+/// * `store_id` - The identifier of the store the role applies to, or `None` for a global assignment.
+/// * `assigned_at` - The timestamp when the role was assigned.
+#[derive(Debug, Serialize)]
+pub struct RoleMemberDTO {
+    pub user_id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub store_id: Option<i32>,
+    pub assigned_at: DateTime<Utc>,
 }
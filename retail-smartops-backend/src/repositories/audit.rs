@@ -0,0 +1,133 @@
+use crate::errors::AppError;
+use crate::models::audit::{AuditEventDTO, AuditQuery, CreateAuditEventDTO};
+use crate::models::list::Page;
+use axum::async_trait;
+use sqlx::PgPool;
+
+/// Repository for audit log database operations.
+pub struct AuditRepository {
+    /// Connection pool for the PostgreSQL database.
+    pool: PgPool,
+}
+
+impl AuditRepository {
+    /// Creates a new instance of `AuditRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool for the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Trait defining the audit repository operations.
+///
+/// The audit log is append-only: events can be recorded and queried, never changed.
+#[async_trait]
+pub trait AuditRepositoryTrait: Send + Sync {
+    /// Records an audit event.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The data transfer object describing the change.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the event was recorded, or an `AppError`.
+    async fn create_event(&self, event: CreateAuditEventDTO) -> Result<(), AppError>;
+
+    /// Retrieves a page of audit events, sortable by `occurred_at` and newest first by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The pagination, sort and filter parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Page<AuditEventDTO>, AppError>` - A page of audit events or an `AppError`.
+    async fn get_events(&self, query: &AuditQuery) -> Result<Page<AuditEventDTO>, AppError>;
+}
+
+#[async_trait]
+impl AuditRepositoryTrait for AuditRepository {
+    async fn create_event(&self, event: CreateAuditEventDTO) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO audit_events (actor_id, action, entity, entity_id, before, after, ip_address, request_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            event.actor_id,
+            event.action.as_str(),
+            event.entity.as_str(),
+            event.entity_id,
+            event.before,
+            event.after,
+            event.ip_address,
+            event.request_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_events(&self, query: &AuditQuery) -> Result<Page<AuditEventDTO>, AppError> {
+        let page = query.page();
+        let sort = page.sort_key(&["occurred_at"], "-occurred_at")?;
+        let offset = page.offset()?;
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM audit_events
+            WHERE ($1::UUID IS NULL OR actor_id = $1)
+              AND ($2::TEXT IS NULL OR action = $2)
+              AND ($3::TEXT IS NULL OR entity = $3)
+              AND ($4::TEXT IS NULL OR entity_id = $4)
+              AND ($5::TIMESTAMPTZ IS NULL OR occurred_at >= $5)
+              AND ($6::TIMESTAMPTZ IS NULL OR occurred_at < $6)
+            "#,
+            query.actor_id,
+            query.action,
+            query.entity,
+            query.entity_id,
+            query.since,
+            query.until
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let events = sqlx::query_as!(
+            AuditEventDTO,
+            r#"
+            SELECT id, occurred_at, actor_id, action, entity, entity_id, before, after, ip_address, request_id
+            FROM audit_events
+            WHERE ($1::UUID IS NULL OR actor_id = $1)
+              AND ($2::TEXT IS NULL OR action = $2)
+              AND ($3::TEXT IS NULL OR entity = $3)
+              AND ($4::TEXT IS NULL OR entity_id = $4)
+              AND ($5::TIMESTAMPTZ IS NULL OR occurred_at >= $5)
+              AND ($6::TIMESTAMPTZ IS NULL OR occurred_at < $6)
+            ORDER BY CASE WHEN $7 = 'occurred_at' THEN occurred_at END,
+                     CASE WHEN $7 = 'occurred_at' THEN id END,
+                     occurred_at DESC,
+                     id DESC
+            LIMIT $8 OFFSET $9
+            "#,
+            query.actor_id,
+            query.action,
+            query.entity,
+            query.entity_id,
+            query.since,
+            query.until,
+            sort,
+            page.limit(),
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Page::new(events, total, offset))
+    }
+}
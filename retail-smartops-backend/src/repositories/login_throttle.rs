@@ -0,0 +1,379 @@
+use crate::errors::AppError;
+use crate::models::auth::{
+    LoginAttemptDTO, LoginThrottleDTO, LoginThrottlePolicy, LoginThrottleScope,
+};
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+/// The largest number of doublings of a lockout, so its duration cannot overflow.
+const MAX_LOCKOUT_DOUBLINGS: i32 = 62;
+
+/// Repository for the failed login counters.
+pub struct LoginThrottleRepository {
+    /// Connection pool for the PostgreSQL database.
+    pool: PgPool,
+}
+
+impl LoginThrottleRepository {
+    /// Creates a new instance of `LoginThrottleRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool for the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Trait defining the login throttle repository operations.
+///
+/// Failed logins are counted per scope and key, e.g. per username or per IP address.
+#[async_trait]
+pub trait LoginThrottleRepositoryTrait: Send + Sync {
+    /// Counts a login attempt as failed and locks the key out once it reaches the threshold.
+    ///
+    /// Attempts are counted before the password is verified, so concurrent guesses cannot all
+    /// slip in before the first failure is recorded; a successful login takes its attempt back
+    /// with `forgive` or `clear`. The check, the count and the lockout are a single statement.
+    ///
+    /// The count starts over once the window has passed since the last failure and the end
+    /// of the last lockout. The first lockout lasts `lockout_base` seconds, every further
+    /// failure doubles it, up to `lockout_max` seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - What the key identifies.
+    /// * `key` - The username or IP address.
+    /// * `policy` - The threshold, window and lockout durations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<LoginAttemptDTO, AppError>` - The count and lockout of the key, or an `AppError`.
+    async fn record_failure(
+        &self,
+        scope: LoginThrottleScope,
+        key: &str,
+        policy: &LoginThrottlePolicy,
+    ) -> Result<LoginAttemptDTO, AppError>;
+
+    /// Takes back an attempt counted by `record_failure`.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - What the key identifies.
+    /// * `key` - The username or IP address.
+    /// * `locked_until` - The lockout set by the attempt, if any, which is lifted as well.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the attempt was taken back, or an `AppError`.
+    async fn forgive(
+        &self,
+        scope: LoginThrottleScope,
+        key: &str,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError>;
+
+    /// Forgets the failed logins and the lockout of a key.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - What the key identifies.
+    /// * `key` - The username or IP address.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<LoginThrottleDTO>, AppError>` - The forgotten counter, `None` if no
+    ///   failed login was counted against the key, or an `AppError`.
+    async fn clear(
+        &self,
+        scope: LoginThrottleScope,
+        key: &str,
+    ) -> Result<Option<LoginThrottleDTO>, AppError>;
+}
+
+#[async_trait]
+impl LoginThrottleRepositoryTrait for LoginThrottleRepository {
+    async fn record_failure(
+        &self,
+        scope: LoginThrottleScope,
+        key: &str,
+        policy: &LoginThrottlePolicy,
+    ) -> Result<LoginAttemptDTO, AppError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO login_throttles (scope, key)
+            VALUES ($1, $2)
+            ON CONFLICT (scope, key) DO NOTHING
+            "#,
+            scope.as_str(),
+            key
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // The row lock taken by the update serializes concurrent attempts on the same key, and
+        // each of them sees the count and lockout left by the previous one.
+        let attempt = sqlx::query_as!(
+            LoginAttemptDTO,
+            r#"
+            UPDATE login_throttles
+            SET failed_attempts = CASE
+                                      WHEN locked_until > CURRENT_TIMESTAMP THEN failed_attempts
+                                      WHEN GREATEST(last_failed_at, locked_until) < CURRENT_TIMESTAMP - make_interval(secs => $3::FLOAT8)
+                                          THEN 1
+                                      ELSE failed_attempts + 1
+                END,
+                last_failed_at  = CASE
+                                      WHEN locked_until > CURRENT_TIMESTAMP THEN last_failed_at
+                                      ELSE CURRENT_TIMESTAMP
+                    END,
+                locked_until    = CASE
+                                      WHEN locked_until > CURRENT_TIMESTAMP THEN locked_until
+                                      WHEN GREATEST(last_failed_at, locked_until) < CURRENT_TIMESTAMP - make_interval(secs => $3::FLOAT8)
+                                          THEN CASE
+                                                   WHEN 1 >= $4::INT
+                                                       THEN CURRENT_TIMESTAMP + make_interval(secs => LEAST($5::FLOAT8, $6::FLOAT8))
+                                          END
+                                      WHEN failed_attempts + 1 >= $4::INT
+                                          THEN CURRENT_TIMESTAMP + make_interval(secs => LEAST(
+                                              $5::FLOAT8 * POWER(2, LEAST(failed_attempts + 1 - $4::INT, $7::INT)), $6::FLOAT8))
+                    END
+            WHERE scope = $1
+              AND key = $2
+            RETURNING failed_attempts, locked_until, last_failed_at = CURRENT_TIMESTAMP AS "counted!"
+            "#,
+            scope.as_str(),
+            key,
+            policy.attempt_window as f64,
+            i32::try_from(policy.max_attempts).unwrap_or(i32::MAX),
+            policy.lockout_base as f64,
+            policy.lockout_max as f64,
+            MAX_LOCKOUT_DOUBLINGS
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(attempt)
+    }
+
+    async fn forgive(
+        &self,
+        scope: LoginThrottleScope,
+        key: &str,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        sqlx::query!(
+            r#"
+            UPDATE login_throttles
+            SET failed_attempts = GREATEST(failed_attempts - 1, 0),
+                locked_until    = CASE WHEN locked_until = $3 THEN NULL ELSE locked_until END
+            WHERE scope = $1
+              AND key = $2
+            "#,
+            scope.as_str(),
+            key,
+            locked_until
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn clear(
+        &self,
+        scope: LoginThrottleScope,
+        key: &str,
+    ) -> Result<Option<LoginThrottleDTO>, AppError> {
+        let throttle = sqlx::query_as!(
+            LoginThrottleDTO,
+            r#"
+            DELETE
+            FROM login_throttles
+            WHERE scope = $1
+              AND key = $2
+            RETURNING failed_attempts, last_failed_at, locked_until
+            "#,
+            scope.as_str(),
+            key
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(throttle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "alice";
+
+    const POLICY: LoginThrottlePolicy = LoginThrottlePolicy {
+        max_attempts: 2,
+        attempt_window: 900,
+        lockout_base: 10,
+        lockout_max: 30,
+    };
+
+    async fn fail(repo: &LoginThrottleRepository) -> LoginAttemptDTO {
+        repo.record_failure(LoginThrottleScope::Username, KEY, &POLICY)
+            .await
+            .unwrap()
+    }
+
+    /// Returns the duration of the current lockout of the key, in seconds.
+    async fn lockout_secs(pool: &PgPool) -> f64 {
+        sqlx::query_scalar(
+            "SELECT EXTRACT(EPOCH FROM locked_until - last_failed_at)::FLOAT8 \
+             FROM login_throttles WHERE scope = 'username' AND key = $1",
+        )
+        .bind(KEY)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    /// Ends the current lockout of the key without touching its count.
+    async fn expire_lockout(pool: &PgPool) {
+        sqlx::query(
+            "UPDATE login_throttles SET locked_until = CURRENT_TIMESTAMP - INTERVAL '1 second' \
+             WHERE scope = 'username' AND key = $1",
+        )
+        .bind(KEY)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn locks_out_at_threshold(pool: PgPool) {
+        let repo = LoginThrottleRepository::new(pool.clone());
+
+        let first = fail(&repo).await;
+        assert_eq!(first.failed_attempts, 1);
+        assert!(first.counted);
+        assert!(first.locked_until.is_none());
+
+        let second = fail(&repo).await;
+        assert_eq!(second.failed_attempts, 2);
+        assert!(second.counted);
+        assert!(second.locked_until.is_some());
+        assert_eq!(lockout_secs(&pool).await, 10.0);
+    }
+
+    #[sqlx::test]
+    async fn does_not_count_attempts_while_locked(pool: PgPool) {
+        let repo = LoginThrottleRepository::new(pool);
+        fail(&repo).await;
+        let locked = fail(&repo).await;
+
+        let attempt = fail(&repo).await;
+
+        assert!(!attempt.counted);
+        assert_eq!(attempt.failed_attempts, 2);
+        assert_eq!(attempt.locked_until, locked.locked_until);
+    }
+
+    #[sqlx::test]
+    async fn counts_concurrent_attempts_up_to_threshold(pool: PgPool) {
+        let repo = std::sync::Arc::new(LoginThrottleRepository::new(pool));
+
+        let attempts: Vec<_> = (0..10)
+            .map(|_| {
+                let repo = repo.clone();
+                tokio::spawn(async move { fail(&repo).await })
+            })
+            .collect();
+        let mut counted = 0;
+        for attempt in attempts {
+            if attempt.await.unwrap().counted {
+                counted += 1;
+            }
+        }
+
+        assert_eq!(counted, POLICY.max_attempts);
+    }
+
+    #[sqlx::test]
+    async fn doubles_lockout_up_to_max(pool: PgPool) {
+        let repo = LoginThrottleRepository::new(pool.clone());
+        fail(&repo).await;
+        fail(&repo).await;
+
+        let mut lockouts = vec![lockout_secs(&pool).await];
+        for _ in 0..3 {
+            expire_lockout(&pool).await;
+            assert!(fail(&repo).await.counted);
+            lockouts.push(lockout_secs(&pool).await);
+        }
+
+        assert_eq!(lockouts, vec![10.0, 20.0, 30.0, 30.0]);
+    }
+
+    #[sqlx::test]
+    async fn starts_over_after_window(pool: PgPool) {
+        let repo = LoginThrottleRepository::new(pool.clone());
+        fail(&repo).await;
+        fail(&repo).await;
+        sqlx::query(
+            "UPDATE login_throttles \
+             SET last_failed_at = CURRENT_TIMESTAMP - INTERVAL '1 hour', \
+                 locked_until   = CURRENT_TIMESTAMP - INTERVAL '1 hour'",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let attempt = fail(&repo).await;
+
+        assert_eq!(attempt.failed_attempts, 1);
+        assert!(attempt.locked_until.is_none());
+    }
+
+    #[sqlx::test]
+    async fn counts_keys_separately(pool: PgPool) {
+        let repo = LoginThrottleRepository::new(pool);
+        fail(&repo).await;
+        fail(&repo).await;
+
+        let other_user = repo
+            .record_failure(LoginThrottleScope::Username, "bob", &POLICY)
+            .await
+            .unwrap();
+        let same_key_as_ip = repo
+            .record_failure(LoginThrottleScope::Ip, KEY, &POLICY)
+            .await
+            .unwrap();
+
+        assert_eq!(other_user.failed_attempts, 1);
+        assert_eq!(same_key_as_ip.failed_attempts, 1);
+    }
+
+    #[sqlx::test]
+    async fn forgive_takes_back_attempt_and_its_lockout(pool: PgPool) {
+        let repo = LoginThrottleRepository::new(pool);
+        fail(&repo).await;
+        let locked = fail(&repo).await;
+
+        repo.forgive(LoginThrottleScope::Username, KEY, locked.locked_until)
+            .await
+            .unwrap();
+
+        let throttle = repo
+            .clear(LoginThrottleScope::Username, KEY)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(throttle.failed_attempts, 1);
+        assert!(throttle.locked_until.is_none());
+        assert!(repo
+            .clear(LoginThrottleScope::Username, KEY)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,9 +1,26 @@
+use crate::repositories::audit::AuditRepositoryTrait;
+use crate::repositories::login_throttle::LoginThrottleRepositoryTrait;
+use crate::repositories::permission::PermissionRepositoryTrait;
 use crate::repositories::role::RoleRepositoryTrait;
+use crate::repositories::role_permission::RolePermissionRepositoryTrait;
+use crate::repositories::session::SessionRepositoryTrait;
+use crate::repositories::store::StoreRepositoryTrait;
+use crate::repositories::store_users::StoreUserRepositoryTrait;
 use crate::repositories::user::UserRepositoryTrait;
+use crate::repositories::user_hierarchy::UserHierarchyRepositoryTrait;
+use crate::repositories::user_role::UserRoleRepositoryTrait;
 use sqlx::PgPool;
 
+mod audit;
+mod login_throttle;
+mod permission;
 mod role;
+mod role_permission;
+mod session;
+mod store;
+mod store_users;
 mod user;
+mod user_hierarchy;
 mod user_role;
 
 /// Container for all repository instances.
@@ -14,6 +31,15 @@ pub struct RepositoryContainer {
     /// The user repository instance.
     pub user_repo: Box<dyn UserRepositoryTrait>,
     pub role_repo: Box<dyn RoleRepositoryTrait>,
+    pub session_repo: Box<dyn SessionRepositoryTrait>,
+    pub permission_repo: Box<dyn PermissionRepositoryTrait>,
+    pub role_permission_repo: Box<dyn RolePermissionRepositoryTrait>,
+    pub user_role_repo: Box<dyn UserRoleRepositoryTrait>,
+    pub store_repo: Box<dyn StoreRepositoryTrait>,
+    pub store_user_repo: Box<dyn StoreUserRepositoryTrait>,
+    pub user_hierarchy_repo: Box<dyn UserHierarchyRepositoryTrait>,
+    pub audit_repo: Box<dyn AuditRepositoryTrait>,
+    pub login_throttle_repo: Box<dyn LoginThrottleRepositoryTrait>,
 }
 
 impl RepositoryContainer {
@@ -29,9 +55,30 @@ impl RepositoryContainer {
     pub fn new(pool: PgPool) -> Self {
         let user_repo = Box::new(user::UserRepository::new(pool.clone()));
         let role_repo = Box::new(role::RoleRepository::new(pool.clone()));
+        let session_repo = Box::new(session::SessionRepository::new(pool.clone()));
+        let permission_repo = Box::new(permission::PermissionRepository::new(pool.clone()));
+        let role_permission_repo =
+            Box::new(role_permission::RolePermissionRepository::new(pool.clone()));
+        let user_role_repo = Box::new(user_role::UserRoleRepository::new(pool.clone()));
+        let store_repo = Box::new(store::StoreRepository::new(pool.clone()));
+        let store_user_repo = Box::new(store_users::StoreUserRepository::new(pool.clone()));
+        let user_hierarchy_repo =
+            Box::new(user_hierarchy::UserHierarchyRepository::new(pool.clone()));
+        let audit_repo = Box::new(audit::AuditRepository::new(pool.clone()));
+        let login_throttle_repo =
+            Box::new(login_throttle::LoginThrottleRepository::new(pool.clone()));
         Self {
             user_repo,
             role_repo,
+            session_repo,
+            permission_repo,
+            role_permission_repo,
+            user_role_repo,
+            store_repo,
+            store_user_repo,
+            user_hierarchy_repo,
+            audit_repo,
+            login_throttle_repo,
         }
     }
 }
@@ -0,0 +1,235 @@
+use crate::entities::permission::Permission;
+use crate::errors::AppError;
+use crate::models::permission::{CreatePermissionDTO, PermissionResponseDTO, UpdatePermissionDTO};
+use axum::async_trait;
+use sqlx::PgPool;
+
+/// Repository for permission-related database operations.
+pub struct PermissionRepository {
+    /// Connection pool for the PostgreSQL database.
+    pool: PgPool,
+}
+
+impl PermissionRepository {
+    /// Creates a new instance of `PermissionRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool for the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Checks if a permission with the given ID exists in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the permission to check.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, AppError>` - `Ok(true)` if the permission exists, `Ok(false)` otherwise, or an `AppError`.
+    async fn check_if_id_exists(&self, id: i32) -> Result<bool, AppError> {
+        let permission_count = sqlx::query!(
+            "SELECT COUNT(*) as count FROM permissions WHERE id = $1",
+            id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .count;
+
+        let count = match permission_count {
+            Some(count) => count,
+            None => {
+                return Err(AppError::InternalServerError(
+                    "Failed to check if permission exists".to_string(),
+                ))
+            }
+        };
+
+        Ok(count > 0)
+    }
+}
+
+/// Trait defining the permission repository operations.
+#[async_trait]
+pub trait PermissionRepositoryTrait: Send + Sync {
+    /// Creates a new permission in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The data transfer object containing permission creation details.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PermissionResponseDTO, AppError>` - The created permission or an `AppError`.
+    async fn create_permission(
+        &self,
+        payload: CreatePermissionDTO,
+    ) -> Result<PermissionResponseDTO, AppError>;
+
+    /// Retrieves a permission by its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The permission ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PermissionResponseDTO, AppError>` - The permission details or an `AppError`.
+    async fn get_permission_by_id(&self, id: i32) -> Result<PermissionResponseDTO, AppError>;
+
+    /// Updates an existing permission in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The permission ID.
+    /// * `payload` - The data transfer object containing permission update details.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PermissionResponseDTO, AppError>` - The updated permission or an `AppError`.
+    async fn update_permission(
+        &self,
+        id: i32,
+        payload: UpdatePermissionDTO,
+    ) -> Result<PermissionResponseDTO, AppError>;
+
+    /// Deletes a permission from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The permission ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the permission was deleted, or an `AppError`.
+    async fn delete_permission(&self, id: i32) -> Result<(), AppError>;
+
+    /// Retrieves all permissions from the database.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<PermissionResponseDTO>, AppError>` - A list of permissions or an `AppError`.
+    async fn get_permissions(&self) -> Result<Vec<PermissionResponseDTO>, AppError>;
+}
+
+#[async_trait]
+impl PermissionRepositoryTrait for PermissionRepository {
+    async fn create_permission(
+        &self,
+        payload: CreatePermissionDTO,
+    ) -> Result<PermissionResponseDTO, AppError> {
+        let permission = sqlx::query_as!(
+            PermissionResponseDTO,
+            r#"
+            INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+            VALUES ($1, COALESCE($2, TRUE), COALESCE($3, FALSE), COALESCE($4, FALSE), COALESCE($5, FALSE))
+            RETURNING id, entity_name, can_read, can_write, can_delete, can_update
+            "#,
+            payload.entity_name,
+            payload.can_read,
+            payload.can_write,
+            payload.can_delete,
+            payload.can_update
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(permission)
+    }
+
+    async fn get_permission_by_id(&self, id: i32) -> Result<PermissionResponseDTO, AppError> {
+        let permission_option = sqlx::query_as!(
+            Permission,
+            r#"
+            SELECT id, entity_name, can_read, can_write, can_delete, can_update
+            FROM permissions
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match permission_option {
+            Some(permission) => Ok(permission.into()),
+            None => Err(AppError::not_found(
+                "permission_not_found",
+                "Permission not found",
+            )),
+        }
+    }
+
+    async fn update_permission(
+        &self,
+        id: i32,
+        payload: UpdatePermissionDTO,
+    ) -> Result<PermissionResponseDTO, AppError> {
+        if !self.check_if_id_exists(id).await? {
+            return Err(AppError::not_found(
+                "permission_not_found",
+                "Permission not found",
+            ));
+        }
+
+        let permission = sqlx::query_as!(
+            PermissionResponseDTO,
+            r#"
+            UPDATE permissions
+            SET entity_name = COALESCE($2, entity_name),
+                can_read = COALESCE($3, can_read),
+                can_write = COALESCE($4, can_write),
+                can_delete = COALESCE($5, can_delete),
+                can_update = COALESCE($6, can_update)
+            WHERE id = $1
+            RETURNING id, entity_name, can_read, can_write, can_delete, can_update
+            "#,
+            id,
+            payload.entity_name,
+            payload.can_read,
+            payload.can_write,
+            payload.can_delete,
+            payload.can_update
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(permission)
+    }
+
+    async fn delete_permission(&self, id: i32) -> Result<(), AppError> {
+        if !self.check_if_id_exists(id).await? {
+            return Err(AppError::not_found(
+                "permission_not_found",
+                "Permission not found",
+            ));
+        }
+
+        let query_result = sqlx::query!("DELETE FROM permissions WHERE id = $1", id)
+            .execute(&self.pool)
+            .await?;
+
+        if query_result.rows_affected() == 0 {
+            return Err(AppError::InternalServerError(
+                "Failed to delete permission".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn get_permissions(&self) -> Result<Vec<PermissionResponseDTO>, AppError> {
+        let permissions = sqlx::query_as!(
+            PermissionResponseDTO,
+            r#"
+            SELECT id, entity_name, can_read, can_write, can_delete, can_update
+            FROM permissions
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(permissions)
+    }
+}
@@ -1,4 +1,6 @@
+use crate::entities::role::Role;
 use crate::errors::AppError;
+use crate::models::list::{ListQuery, Page};
 use crate::models::role::{CreateRoleDTO, RoleResponseDTO, UpdateRoleDTO};
 use axum::async_trait;
 use sqlx::PgPool;
@@ -126,19 +128,26 @@ pub trait RoleRepositoryTrait: Send + Sync {
     /// * `Result<(), AppError>` - `Ok(())` if the role was deleted, or an `AppError`.
     async fn delete_role(&self, id: i32) -> Result<(), AppError>;
 
-    /// Retrieves all roles from the database.
+    /// Retrieves a page of roles from the database, sortable by `id` or `name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The pagination and sort parameters.
     ///
     /// # Returns
     ///
-    /// * `Result<Vec<RoleResponseDTO>, AppError>` - A list of roles or an `AppError`.
-    async fn get_roles(&self) -> Result<Vec<RoleResponseDTO>, AppError>;
+    /// * `Result<Page<RoleResponseDTO>, AppError>` - A page of roles or an `AppError`.
+    async fn get_roles(&self, query: &ListQuery) -> Result<Page<RoleResponseDTO>, AppError>;
 }
 
 #[async_trait]
 impl RoleRepositoryTrait for RoleRepository {
     async fn create_role(&self, payload: CreateRoleDTO) -> Result<RoleResponseDTO, AppError> {
         if self.check_if_role_exists(&payload.name).await? {
-            return Err(AppError::Conflict);
+            return Err(AppError::conflict(
+                "role_name_taken",
+                "A role with this name already exists",
+            ));
         }
 
         let role = sqlx::query_as!(
@@ -153,17 +162,13 @@ impl RoleRepositoryTrait for RoleRepository {
     }
 
     async fn get_role_by_id(&self, id: i32) -> Result<RoleResponseDTO, AppError> {
-        let role_option = sqlx::query_as!(
-            RoleResponseDTO,
-            r#"SELECT id, name FROM roles WHERE id = $1"#,
-            id
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+        let role_option = sqlx::query_as!(Role, r#"SELECT id, name FROM roles WHERE id = $1"#, id)
+            .fetch_optional(&self.pool)
+            .await?;
 
         match role_option {
-            Some(role) => Ok(role),
-            None => Err(AppError::NotFound),
+            Some(role) => Ok(role.into()),
+            None => Err(AppError::not_found("role_not_found", "Role not found")),
         }
     }
 
@@ -172,8 +177,15 @@ impl RoleRepositoryTrait for RoleRepository {
         id: i32,
         payload: UpdateRoleDTO,
     ) -> Result<RoleResponseDTO, AppError> {
-        if !self.check_if_id_exists(id).await? {
-            return Err(AppError::NotFound);
+        let current_role = self.get_role_by_id(id).await?;
+
+        if let Some(name) = &payload.name {
+            if *name != current_role.name && self.check_if_role_exists(name).await? {
+                return Err(AppError::conflict(
+                    "role_name_taken",
+                    "A role with this name already exists",
+                ));
+            }
         }
 
         let role = sqlx::query_as!(
@@ -190,7 +202,7 @@ impl RoleRepositoryTrait for RoleRepository {
 
     async fn delete_role(&self, id: i32) -> Result<(), AppError> {
         if !self.check_if_id_exists(id).await? {
-            return Err(AppError::NotFound);
+            return Err(AppError::not_found("role_not_found", "Role not found"));
         }
 
         let query_result = sqlx::query!("DELETE FROM roles WHERE id = $1", id)
@@ -206,11 +218,32 @@ impl RoleRepositoryTrait for RoleRepository {
         Ok(())
     }
 
-    async fn get_roles(&self) -> Result<Vec<RoleResponseDTO>, AppError> {
-        let roles = sqlx::query_as!(RoleResponseDTO, r#"SELECT id, name FROM roles"#)
-            .fetch_all(&self.pool)
+    async fn get_roles(&self, query: &ListQuery) -> Result<Page<RoleResponseDTO>, AppError> {
+        let sort = query.sort_key(&["id", "name"], "id")?;
+        let offset = query.offset()?;
+
+        let total = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM roles"#)
+            .fetch_one(&self.pool)
             .await?;
 
-        Ok(roles)
+        let roles = sqlx::query_as!(
+            RoleResponseDTO,
+            r#"
+            SELECT id, name
+            FROM roles
+            ORDER BY CASE WHEN $1 = 'name' THEN name END,
+                     CASE WHEN $1 = '-name' THEN name END DESC,
+                     CASE WHEN $1 = '-id' THEN id END DESC,
+                     id
+            LIMIT $2 OFFSET $3
+            "#,
+            sort,
+            query.limit(),
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Page::new(roles, total, offset))
     }
 }
@@ -0,0 +1,260 @@
+use crate::entities::role_permission::RolePermission;
+use crate::errors::AppError;
+use crate::models::permission::PermissionResponseDTO;
+use crate::models::role_permission::RolePermissionResponseDTO;
+use axum::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Repository for managing role permissions in the database.
+pub struct RolePermissionRepository {
+    pool: PgPool,
+}
+
+impl RolePermissionRepository {
+    /// Creates a new instance of `RolePermissionRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool to the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Checks if a role permission exists in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    /// * `permission_id` - The ID of the permission.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, AppError>` - Returns `Ok(true)` if the role permission exists, `Ok(false)` otherwise.
+    ///   Returns an `AppError` if an error occurs.
+    async fn check_if_role_permission_exists(
+        &self,
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<bool, AppError> {
+        let role_permission_count = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM role_permissions
+            WHERE role_id = $1 AND permission_id = $2
+            "#,
+            role_id,
+            permission_id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .count;
+
+        let count = match role_permission_count {
+            Some(count) => count,
+            None => {
+                return Err(AppError::InternalServerError(
+                    "Failed to check if role permission exists".to_string(),
+                ))
+            }
+        };
+
+        Ok(count > 0)
+    }
+}
+
+/// Trait defining the operations for managing role permissions.
+#[async_trait]
+pub trait RolePermissionRepositoryTrait: Send + Sync {
+    /// Assigns a permission to a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    /// * `permission_id` - The ID of the permission.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<RolePermissionResponseDTO, AppError>` - Returns the added role permission or an `AppError` if an error occurs.
+    async fn add_role_permission(
+        &self,
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<RolePermissionResponseDTO, AppError>;
+
+    /// Removes a permission from a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    /// * `permission_id` - The ID of the permission.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - Returns `Ok(())` if the role permission was deleted or an `AppError` if an error occurs.
+    async fn delete_role_permission(
+        &self,
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<(), AppError>;
+
+    /// Lists the permissions assigned to a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<PermissionResponseDTO>, AppError>` - The permissions of the role or an `AppError`.
+    async fn get_role_permissions(
+        &self,
+        role_id: i32,
+    ) -> Result<Vec<PermissionResponseDTO>, AppError>;
+
+    /// Resolves the effective permissions a user holds on an entity through their roles.
+    ///
+    /// Global role assignments always count. Store-scoped assignments only count when
+    /// `store_id` names the store they were granted in and the user is a member of that store
+    /// today, so seasonal staff lose them once their membership period ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    /// * `entity_name` - The name of the entity.
+    /// * `store_id` - The ID of the store being accessed, or `None` outside of a store.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<PermissionResponseDTO>, AppError>` - The permissions granted to the user on the entity
+    ///   or an `AppError`.
+    async fn get_user_permissions(
+        &self,
+        user_id: Uuid,
+        entity_name: &str,
+        store_id: Option<i32>,
+    ) -> Result<Vec<PermissionResponseDTO>, AppError>;
+}
+
+#[async_trait]
+impl RolePermissionRepositoryTrait for RolePermissionRepository {
+    async fn add_role_permission(
+        &self,
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<RolePermissionResponseDTO, AppError> {
+        if self
+            .check_if_role_permission_exists(role_id, permission_id)
+            .await?
+        {
+            return Err(AppError::conflict(
+                "role_permission_exists",
+                "The role already holds this permission",
+            ));
+        }
+
+        let role_permission = sqlx::query_as!(
+            RolePermission,
+            r#"
+            INSERT INTO role_permissions (role_id, permission_id)
+            VALUES ($1, $2)
+            RETURNING role_id, permission_id
+            "#,
+            role_id,
+            permission_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(role_permission.into())
+    }
+
+    async fn delete_role_permission(
+        &self,
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<(), AppError> {
+        if !self
+            .check_if_role_permission_exists(role_id, permission_id)
+            .await?
+        {
+            return Err(AppError::not_found(
+                "role_permission_not_found",
+                "The role does not hold this permission",
+            ));
+        }
+
+        let query_result = sqlx::query!(
+            r#"
+            DELETE FROM role_permissions
+            WHERE role_id = $1 AND permission_id = $2
+            "#,
+            role_id,
+            permission_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if query_result.rows_affected() == 0 {
+            return Err(AppError::InternalServerError(
+                "Failed to delete role permission".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn get_role_permissions(
+        &self,
+        role_id: i32,
+    ) -> Result<Vec<PermissionResponseDTO>, AppError> {
+        let permissions = sqlx::query_as!(
+            PermissionResponseDTO,
+            r#"
+            SELECT p.id, p.entity_name, p.can_read, p.can_write, p.can_delete, p.can_update
+            FROM permissions p
+            JOIN role_permissions rp ON rp.permission_id = p.id
+            WHERE rp.role_id = $1
+            "#,
+            role_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(permissions)
+    }
+
+    async fn get_user_permissions(
+        &self,
+        user_id: Uuid,
+        entity_name: &str,
+        store_id: Option<i32>,
+    ) -> Result<Vec<PermissionResponseDTO>, AppError> {
+        let permissions = sqlx::query_as!(
+            PermissionResponseDTO,
+            r#"
+            SELECT DISTINCT p.id, p.entity_name, p.can_read, p.can_write, p.can_delete, p.can_update
+            FROM permissions p
+            JOIN role_permissions rp ON rp.permission_id = p.id
+            JOIN user_roles ur ON ur.role_id = rp.role_id
+            WHERE ur.user_id = $1
+              AND p.entity_name = $2
+              AND (ur.store_id IS NULL
+                OR (ur.store_id = $3
+                    AND EXISTS (SELECT 1
+                                FROM store_users su
+                                WHERE su.user_id = ur.user_id
+                                  AND su.store_id = ur.store_id
+                                  AND (su.starts_on IS NULL OR su.starts_on <= CURRENT_DATE)
+                                  AND (su.ends_on IS NULL OR su.ends_on >= CURRENT_DATE))))
+            "#,
+            user_id,
+            entity_name,
+            store_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(permissions)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::entities::session::Session;
+use crate::errors::AppError;
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Repository for session-related database operations.
+pub struct SessionRepository {
+    /// Connection pool for the PostgreSQL database.
+    pool: PgPool,
+}
+
+impl SessionRepository {
+    /// Creates a new instance of `SessionRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool for the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Trait defining the session repository operations.
+#[async_trait]
+pub trait SessionRepositoryTrait: Send + Sync {
+    /// Creates a new session in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user owning the session.
+    /// * `refresh_token_hash` - The hash of the refresh token issued for the session.
+    /// * `expires_at` - The timestamp when the session expires.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Session, AppError>` - The created session or an `AppError`.
+    async fn create_session(
+        &self,
+        user_id: Uuid,
+        refresh_token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Session, AppError>;
+
+    /// Revokes the active session a refresh token belongs to.
+    ///
+    /// A session is active when it has neither expired nor been revoked. The check and the
+    /// revocation are a single statement, so a refresh token can be redeemed only once even
+    /// when it is presented by concurrent requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `refresh_token_hash` - The hash of the refresh token.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Session, AppError>` - The revoked session or `AppError::NotFound` if no active
+    ///   session matches.
+    async fn revoke_active_session(&self, refresh_token_hash: &str) -> Result<Session, AppError>;
+
+    /// Revokes every active session of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u64, AppError>` - The number of revoked sessions or an `AppError`.
+    async fn revoke_user_sessions(&self, user_id: Uuid) -> Result<u64, AppError>;
+}
+
+#[async_trait]
+impl SessionRepositoryTrait for SessionRepository {
+    async fn create_session(
+        &self,
+        user_id: Uuid,
+        refresh_token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Session, AppError> {
+        let session = sqlx::query_as!(
+            Session,
+            r#"
+            INSERT INTO sessions (user_id, refresh_token_hash, expires_at)
+            VALUES ($1, $2, $3)
+            RETURNING id, user_id, refresh_token_hash, created_at, expires_at, revoked_at
+            "#,
+            user_id,
+            refresh_token_hash,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    async fn revoke_active_session(&self, refresh_token_hash: &str) -> Result<Session, AppError> {
+        let session_optional = sqlx::query_as!(
+            Session,
+            r#"
+            UPDATE sessions
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE refresh_token_hash = $1
+              AND revoked_at IS NULL
+              AND expires_at > CURRENT_TIMESTAMP
+            RETURNING id, user_id, refresh_token_hash, created_at, expires_at, revoked_at
+            "#,
+            refresh_token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match session_optional {
+            Some(session) => Ok(session),
+            None => Err(AppError::not_found(
+                "session_not_found",
+                "No active session matches the token",
+            )),
+        }
+    }
+
+    async fn revoke_user_sessions(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let query_result = sqlx::query!(
+            r#"
+            UPDATE sessions
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(query_result.rows_affected())
+    }
+}
@@ -0,0 +1,245 @@
+use crate::entities::store::{Address, Store};
+use crate::errors::AppError;
+use crate::models::list::{ListQuery, Page};
+use crate::models::store::{CreateStoreDTO, StoreResponseDTO, UpdateStoreDTO};
+use axum::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// A row of the `stores` table.
+///
+/// The table keeps the address in flat columns while the `Store` entity nests it in an
+/// `Address`, so queries read into this struct and convert it.
+struct StoreRow {
+    store_id: i32,
+    store_name: String,
+    country: String,
+    state: String,
+    city: String,
+    street: String,
+    zip: String,
+    owner_id: Uuid,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<StoreRow> for Store {
+    fn from(row: StoreRow) -> Self {
+        Self {
+            id: row.store_id,
+            owner_id: row.owner_id,
+            name: row.store_name,
+            address: Address {
+                country: row.country,
+                state: row.state,
+                city: row.city,
+                street: row.street,
+                zip: row.zip,
+            },
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Repository for store-related database operations.
+pub struct StoreRepository {
+    /// Connection pool for the PostgreSQL database.
+    pool: PgPool,
+}
+
+impl StoreRepository {
+    /// Creates a new instance of `StoreRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool for the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Trait defining the store repository operations.
+#[async_trait]
+pub trait StoreRepositoryTrait: Send + Sync {
+    /// Creates a new store in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The data transfer object containing store creation details.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Store, AppError>` - The created store or an `AppError`.
+    async fn create_store(&self, payload: CreateStoreDTO) -> Result<Store, AppError>;
+
+    /// Retrieves a store by its ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The store ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Store, AppError>` - The store or `AppError::NotFound`.
+    async fn get_store_by_id(&self, id: i32) -> Result<Store, AppError>;
+
+    /// Updates an existing store in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The store ID.
+    /// * `payload` - The data transfer object containing store update details.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Store, AppError>` - The updated store, `AppError::NotFound`, or an `AppError`.
+    async fn update_store(&self, id: i32, payload: UpdateStoreDTO) -> Result<Store, AppError>;
+
+    /// Deletes a store from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The store ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the store was deleted, `AppError::NotFound`, or an `AppError`.
+    async fn delete_store(&self, id: i32) -> Result<(), AppError>;
+
+    /// Retrieves a page of stores from the database, sortable by `id` or `name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The pagination and sort parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Page<StoreResponseDTO>, AppError>` - A page of stores or an `AppError`.
+    async fn get_stores(&self, query: &ListQuery) -> Result<Page<StoreResponseDTO>, AppError>;
+}
+
+#[async_trait]
+impl StoreRepositoryTrait for StoreRepository {
+    async fn create_store(&self, payload: CreateStoreDTO) -> Result<Store, AppError> {
+        let store = sqlx::query_as!(
+            StoreRow,
+            r#"
+            INSERT INTO stores (store_name, country, state, city, street, zip, owner_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING store_id, store_name, country, state, city, street, zip, owner_id, created_at, updated_at
+            "#,
+            payload.name,
+            payload.address.country,
+            payload.address.state,
+            payload.address.city,
+            payload.address.street,
+            payload.address.zip,
+            payload.owner_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(store.into())
+    }
+
+    async fn get_store_by_id(&self, id: i32) -> Result<Store, AppError> {
+        let store_option = sqlx::query_as!(
+            StoreRow,
+            r#"
+            SELECT store_id, store_name, country, state, city, street, zip, owner_id, created_at, updated_at
+            FROM stores
+            WHERE store_id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match store_option {
+            Some(store) => Ok(store.into()),
+            None => Err(AppError::not_found("store_not_found", "Store not found")),
+        }
+    }
+
+    async fn update_store(&self, id: i32, payload: UpdateStoreDTO) -> Result<Store, AppError> {
+        let store_option = sqlx::query_as!(
+            StoreRow,
+            r#"
+            UPDATE stores
+            SET store_name = COALESCE($2, store_name),
+                country = COALESCE($3, country),
+                state = COALESCE($4, state),
+                city = COALESCE($5, city),
+                street = COALESCE($6, street),
+                zip = COALESCE($7, zip),
+                owner_id = COALESCE($8, owner_id),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE store_id = $1
+            RETURNING store_id, store_name, country, state, city, street, zip, owner_id, created_at, updated_at
+            "#,
+            id,
+            payload.name,
+            payload.address.country,
+            payload.address.state,
+            payload.address.city,
+            payload.address.street,
+            payload.address.zip,
+            payload.owner_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match store_option {
+            Some(store) => Ok(store.into()),
+            None => Err(AppError::not_found("store_not_found", "Store not found")),
+        }
+    }
+
+    async fn delete_store(&self, id: i32) -> Result<(), AppError> {
+        let query_result = sqlx::query!("DELETE FROM stores WHERE store_id = $1", id)
+            .execute(&self.pool)
+            .await?;
+
+        if query_result.rows_affected() == 0 {
+            return Err(AppError::not_found("store_not_found", "Store not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_stores(&self, query: &ListQuery) -> Result<Page<StoreResponseDTO>, AppError> {
+        let sort = query.sort_key(&["id", "name"], "id")?;
+        let offset = query.offset()?;
+
+        let total = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM stores"#)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let stores = sqlx::query_as!(
+            StoreRow,
+            r#"
+            SELECT store_id, store_name, country, state, city, street, zip, owner_id, created_at, updated_at
+            FROM stores
+            ORDER BY CASE WHEN $1 = 'name' THEN store_name END,
+                     CASE WHEN $1 = '-name' THEN store_name END DESC,
+                     CASE WHEN $1 = '-id' THEN store_id END DESC,
+                     store_id
+            LIMIT $2 OFFSET $3
+            "#,
+            sort,
+            query.limit(),
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let stores = stores
+            .into_iter()
+            .map(|row| StoreResponseDTO::from(Store::from(row)))
+            .collect();
+
+        Ok(Page::new(stores, total, offset))
+    }
+}
@@ -0,0 +1,238 @@
+use crate::entities::store_users::StoreUsers;
+use crate::errors::AppError;
+use crate::models::store_users::{AddStoreUserDTO, StoreStaffDTO, UserStoreDTO};
+use axum::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Repository for managing store memberships in the database.
+///
+/// A membership is current when today falls within its optional start and end dates.
+/// Listings only return current memberships, so seasonal staff drop off once their
+/// period ends without any clean-up.
+pub struct StoreUserRepository {
+    pool: PgPool,
+}
+
+impl StoreUserRepository {
+    /// Creates a new instance of `StoreUserRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool to the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Checks if a store membership exists in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `store_id` - The ID of the store.
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, AppError>` - Returns `Ok(true)` if the membership exists, `Ok(false)` otherwise.
+    ///   Returns an `AppError` if an error occurs.
+    async fn check_if_store_user_exists(
+        &self,
+        store_id: i32,
+        user_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let store_user_count = sqlx::query!(
+            r#"
+            SELECT COUNT(*) as count
+            FROM store_users
+            WHERE store_id = $1 AND user_id = $2
+            "#,
+            store_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await?
+        .count;
+
+        let count = match store_user_count {
+            Some(count) => count,
+            None => {
+                return Err(AppError::InternalServerError(
+                    "Failed to check if store user exists".to_string(),
+                ))
+            }
+        };
+
+        Ok(count > 0)
+    }
+}
+
+/// Trait defining the operations for managing store memberships.
+#[async_trait]
+pub trait StoreUserRepositoryTrait: Send + Sync {
+    /// Adds a user to a store.
+    ///
+    /// # Arguments
+    ///
+    /// * `store_id` - The ID of the store.
+    /// * `payload` - The user joining the store and the optional membership period.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<StoreUsers, AppError>` - Returns the membership, `AppError::Conflict` if the user
+    ///   is already a member, or an `AppError` if an error occurs.
+    async fn add_store_user(
+        &self,
+        store_id: i32,
+        payload: AddStoreUserDTO,
+    ) -> Result<StoreUsers, AppError>;
+
+    /// Removes a user from a store.
+    ///
+    /// # Arguments
+    ///
+    /// * `store_id` - The ID of the store.
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<StoreUsers, AppError>` - Returns the deleted membership, `AppError::NotFound` if the
+    ///   user is not a member, or an `AppError` if an error occurs.
+    async fn remove_store_user(&self, store_id: i32, user_id: Uuid)
+        -> Result<StoreUsers, AppError>;
+
+    /// Lists the current staff of a store with the roles they hold there.
+    ///
+    /// # Arguments
+    ///
+    /// * `store_id` - The ID of the store.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<StoreStaffDTO>, AppError>` - Returns the staff of the store or an `AppError` if an error occurs.
+    async fn list_store_staff(&self, store_id: i32) -> Result<Vec<StoreStaffDTO>, AppError>;
+
+    /// Lists the stores a user currently works at.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<UserStoreDTO>, AppError>` - Returns the stores of the user or an `AppError` if an error occurs.
+    async fn list_user_stores(&self, user_id: Uuid) -> Result<Vec<UserStoreDTO>, AppError>;
+}
+
+#[async_trait]
+impl StoreUserRepositoryTrait for StoreUserRepository {
+    async fn add_store_user(
+        &self,
+        store_id: i32,
+        payload: AddStoreUserDTO,
+    ) -> Result<StoreUsers, AppError> {
+        if self
+            .check_if_store_user_exists(store_id, payload.user_id)
+            .await?
+        {
+            return Err(AppError::conflict(
+                "store_member_exists",
+                "The user is already a member of this store",
+            ));
+        }
+
+        let store_user = sqlx::query_as!(
+            StoreUsers,
+            r#"
+            INSERT INTO store_users (store_id, user_id, starts_on, ends_on)
+            VALUES ($1, $2, $3, $4)
+            RETURNING store_id, user_id, starts_on, ends_on, created_at
+            "#,
+            store_id,
+            payload.user_id,
+            payload.starts_on,
+            payload.ends_on
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(store_user)
+    }
+
+    async fn remove_store_user(
+        &self,
+        store_id: i32,
+        user_id: Uuid,
+    ) -> Result<StoreUsers, AppError> {
+        sqlx::query_as!(
+            StoreUsers,
+            r#"
+            DELETE FROM store_users
+            WHERE store_id = $1 AND user_id = $2
+            RETURNING store_id, user_id, starts_on, ends_on, created_at
+            "#,
+            store_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| {
+            AppError::not_found(
+                "store_member_not_found",
+                "The user is not a member of this store",
+            )
+        })
+    }
+
+    async fn list_store_staff(&self, store_id: i32) -> Result<Vec<StoreStaffDTO>, AppError> {
+        let staff = sqlx::query_as!(
+            StoreStaffDTO,
+            r#"
+            SELECT u.id AS user_id,
+                   u.username,
+                   u.email,
+                   su.starts_on,
+                   su.ends_on,
+                   COALESCE(
+                       ARRAY_AGG(DISTINCT r.name) FILTER (WHERE r.name IS NOT NULL),
+                       '{}'
+                   ) AS "roles!"
+            FROM store_users su
+            JOIN users u ON u.id = su.user_id
+            LEFT JOIN user_roles ur
+                ON ur.user_id = su.user_id AND (ur.store_id IS NULL OR ur.store_id = su.store_id)
+            LEFT JOIN roles r ON r.id = ur.role_id
+            WHERE su.store_id = $1
+              AND u.deleted_at IS NULL
+              AND (su.starts_on IS NULL OR su.starts_on <= CURRENT_DATE)
+              AND (su.ends_on IS NULL OR su.ends_on >= CURRENT_DATE)
+            GROUP BY u.id, su.starts_on, su.ends_on
+            ORDER BY u.username
+            "#,
+            store_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(staff)
+    }
+
+    async fn list_user_stores(&self, user_id: Uuid) -> Result<Vec<UserStoreDTO>, AppError> {
+        let stores = sqlx::query_as!(
+            UserStoreDTO,
+            r#"
+            SELECT s.store_id, s.store_name, su.starts_on, su.ends_on
+            FROM store_users su
+            JOIN stores s ON s.store_id = su.store_id
+            WHERE su.user_id = $1
+              AND (su.starts_on IS NULL OR su.starts_on <= CURRENT_DATE)
+              AND (su.ends_on IS NULL OR su.ends_on >= CURRENT_DATE)
+            ORDER BY s.store_name
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stores)
+    }
+}
@@ -1,7 +1,10 @@
-use crate::errors::AppError;
+use crate::entities::user::User;
+use crate::errors::{AppError, ErrorDetail};
+use crate::models::employee::{EmployeeLifecycleEventDTO, EmployeeLifecycleEventKind};
+use crate::models::list::{ListQuery, Page};
 use crate::models::user::{CreateUserDTO, UpdateUserDTO, UserResponseDTO};
 use axum::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
 /// Repository for user-related database operations.
@@ -82,18 +85,27 @@ impl UserRepository {
 
     /// Checks if a user ID already exists in the database.
     ///
+    /// Takes an executor so the check can run inside the transaction it guards.
+    ///
     /// # Arguments
     ///
+    /// * `executor` - The pool or transaction connection to run the query on.
     /// * `id` - The user ID to check.
     ///
     /// # Returns
     ///
     /// * `Result<bool, AppError>` - `Ok(true)` if the ID exists, `Ok(false)` otherwise, or an `AppError`.
-    async fn check_if_id_exists(&self, id: &Uuid) -> Result<bool, AppError> {
-        let id_count = sqlx::query!("SELECT COUNT(id) as count FROM users WHERE id = $1", id)
-            .fetch_one(&self.pool)
-            .await?
-            .count;
+    async fn check_if_id_exists<'e, E: PgExecutor<'e>>(
+        executor: E,
+        id: &Uuid,
+    ) -> Result<bool, AppError> {
+        let id_count = sqlx::query!(
+            "SELECT COUNT(id) as count FROM users WHERE id = $1 AND deleted_at IS NULL",
+            id
+        )
+        .fetch_one(executor)
+        .await?
+        .count;
 
         let count = match id_count {
             Some(count) => count,
@@ -108,6 +120,16 @@ impl UserRepository {
     }
 }
 
+/// The error returned when no live user has the requested ID or username.
+fn user_not_found() -> AppError {
+    AppError::not_found("user_not_found", "User not found")
+}
+
+/// The error returned when no soft-deleted user has the requested ID.
+fn deleted_user_not_found() -> AppError {
+    AppError::not_found("deleted_user_not_found", "No deleted user has this ID")
+}
+
 /// Trait defining the user repository operations.
 #[async_trait]
 pub trait UserRepositoryTrait: Send + Sync {
@@ -122,6 +144,23 @@ pub trait UserRepositoryTrait: Send + Sync {
     /// * `Result<UserResponseDTO, AppError>` - The created user or an `AppError`.
     async fn create_user(&self, payload: CreateUserDTO) -> Result<UserResponseDTO, AppError>;
 
+    /// Creates the first administrator: a user holding the seeded `admin` role globally.
+    ///
+    /// The `admin` role is locked for the duration of the transaction, so concurrent calls
+    /// cannot both create an administrator.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The new user, with the password already hashed.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<UserResponseDTO, AppError>` - The created user, `AppError::Conflict` if an active
+    ///   user already holds the `admin` role globally or the username or email is taken,
+    ///   `AppError::NotFound` if the `admin` role is missing, or an `AppError`.
+    async fn create_first_admin(&self, payload: CreateUserDTO)
+        -> Result<UserResponseDTO, AppError>;
+
     /// Retrieves a user by their ID.
     ///
     /// # Arguments
@@ -133,6 +172,28 @@ pub trait UserRepositoryTrait: Send + Sync {
     /// * `Result<UserResponseDTO, AppError>` - The user details or an `AppError`.
     async fn get_user_by_id(&self, id: Uuid) -> Result<UserResponseDTO, AppError>;
 
+    /// Retrieves the full user record, including the password hash, by username.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<User, AppError>` - The user entity or an `AppError`.
+    async fn get_user_entity_by_username(&self, username: &str) -> Result<User, AppError>;
+
+    /// Retrieves the full user record by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<User, AppError>` - The user entity or an `AppError`.
+    async fn get_user_entity_by_id(&self, id: Uuid) -> Result<User, AppError>;
+
     /// Updates an existing user in the database.
     ///
     /// # Arguments
@@ -149,7 +210,10 @@ pub trait UserRepositoryTrait: Send + Sync {
         payload: UpdateUserDTO,
     ) -> Result<UserResponseDTO, AppError>;
 
-    /// Deletes a user from the database.
+    /// Soft deletes a user.
+    ///
+    /// The row is kept, with its role assignments and store links, but the user is hidden from
+    /// every default query and their sessions are revoked.
     ///
     /// # Arguments
     ///
@@ -160,21 +224,95 @@ pub trait UserRepositoryTrait: Send + Sync {
     /// * `Result<(), AppError>` - `Ok(())` if the user was deleted, or an `AppError`.
     async fn delete_user(&self, id: Uuid) -> Result<(), AppError>;
 
-    /// Retrieves all users from the database.
+    /// Restores a soft deleted user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
     ///
     /// # Returns
     ///
-    /// * `Result<Vec<UserResponseDTO>, AppError>` - A list of users or an `AppError`.
-    async fn get_all_users(&self) -> Result<Vec<UserResponseDTO>, AppError>;
+    /// * `Result<UserResponseDTO, AppError>` - The restored user, or `AppError::NotFound` if no
+    ///   deleted user has this ID.
+    async fn restore_user(&self, id: Uuid) -> Result<UserResponseDTO, AppError>;
+
+    /// Permanently removes a soft deleted user.
+    ///
+    /// Stores owned by the user are handed to `new_owner_id` before the row is removed, in the
+    /// same transaction. Rows referencing the user through a cascading key go with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `new_owner_id` - The user taking over the stores owned by the purged user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u64, AppError>` - The number of stores reassigned, `AppError::NotFound` if no deleted
+    ///   user has this ID, `AppError::Conflict` if the user owns stores and no new owner is given,
+    ///   or an `AppError`.
+    async fn purge_user(&self, id: Uuid, new_owner_id: Option<Uuid>) -> Result<u64, AppError>;
+
+    /// Retrieves a page of users from the database.
+    ///
+    /// Users can be filtered by `is_active`, `email` and `role`, and sorted by `username`,
+    /// `email` or `created_at`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The pagination, sort and filter parameters.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Page<UserResponseDTO>, AppError>` - A page of users or an `AppError`.
+    async fn get_all_users(&self, query: &ListQuery) -> Result<Page<UserResponseDTO>, AppError>;
+
+    /// Deactivates a user and detaches them from the organisation in a single transaction.
+    ///
+    /// The user's sessions are revoked, their store memberships and reporting line are removed,
+    /// and their direct reports are moved to the user's own manager. When the user has no
+    /// manager, the direct reports become top-level users.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<EmployeeLifecycleEventDTO, AppError>` - The deactivation event, `AppError::NotFound`,
+    ///   `AppError::Conflict` if the user is already inactive, or an `AppError`.
+    async fn deactivate_user(&self, id: Uuid) -> Result<EmployeeLifecycleEventDTO, AppError>;
+
+    /// Reactivates a user.
+    ///
+    /// Store memberships and reporting lines removed on deactivation are not restored.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<EmployeeLifecycleEventDTO, AppError>` - The reactivation event, `AppError::NotFound`,
+    ///   `AppError::Conflict` if the user is already active, or an `AppError`.
+    async fn reactivate_user(&self, id: Uuid) -> Result<EmployeeLifecycleEventDTO, AppError>;
 }
 
 #[async_trait]
 impl UserRepositoryTrait for UserRepository {
     async fn create_user(&self, payload: CreateUserDTO) -> Result<UserResponseDTO, AppError> {
-        if self.check_if_email_exists(&payload.email).await?
-            || self.check_if_username_exists(&payload.username).await?
-        {
-            return Err(AppError::Conflict);
+        if self.check_if_username_exists(&payload.username).await? {
+            return Err(AppError::Conflict(
+                ErrorDetail::new("username_taken", "The username is already taken")
+                    .with_field("username", "is already taken"),
+            ));
+        }
+
+        if self.check_if_email_exists(&payload.email).await? {
+            return Err(AppError::Conflict(
+                ErrorDetail::new("email_taken", "The email is already registered")
+                    .with_field("email", "is already registered"),
+            ));
         }
 
         let user = sqlx::query_as!(
@@ -194,13 +332,92 @@ impl UserRepositoryTrait for UserRepository {
         Ok(user)
     }
 
+    async fn create_first_admin(
+        &self,
+        payload: CreateUserDTO,
+    ) -> Result<UserResponseDTO, AppError> {
+        let mut transaction = self.pool.begin().await?;
+
+        let admin_role_id =
+            sqlx::query_scalar!(r#"SELECT id FROM roles WHERE name = 'admin' FOR UPDATE"#)
+                .fetch_optional(&mut *transaction)
+                .await?
+                .ok_or_else(|| {
+                    AppError::not_found(
+                        "admin_role_not_found",
+                        "The admin role does not exist, run the migrations first",
+                    )
+                })?;
+
+        let admin_exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (SELECT 1
+                           FROM user_roles ur
+                           JOIN users u ON u.id = ur.user_id
+                           WHERE ur.role_id = $1
+                             AND ur.store_id IS NULL
+                             AND u.is_active
+                             AND u.deleted_at IS NULL) AS "exists!"
+            "#,
+            admin_role_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+        if admin_exists {
+            return Err(AppError::conflict(
+                "admin_exists",
+                "An administrator already exists",
+            ));
+        }
+
+        if self.check_if_username_exists(&payload.username).await? {
+            return Err(AppError::Conflict(
+                ErrorDetail::new("username_taken", "The username is already taken")
+                    .with_field("username", "is already taken"),
+            ));
+        }
+
+        if self.check_if_email_exists(&payload.email).await? {
+            return Err(AppError::Conflict(
+                ErrorDetail::new("email_taken", "The email is already registered")
+                    .with_field("email", "is already registered"),
+            ));
+        }
+
+        let user = sqlx::query_as!(
+            UserResponseDTO,
+            r#"
+            INSERT INTO users (username, email, password)
+            VALUES ($1, $2, $3)
+            RETURNING id, username, email
+            "#,
+            payload.username,
+            payload.email,
+            payload.password
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2)"#,
+            user.id,
+            admin_role_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(user)
+    }
+
     async fn get_user_by_id(&self, id: Uuid) -> Result<UserResponseDTO, AppError> {
         let user_optional = sqlx::query_as!(
             UserResponseDTO,
             r#"
             SELECT id, username, email
             FROM users
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
@@ -209,7 +426,45 @@ impl UserRepositoryTrait for UserRepository {
 
         match user_optional {
             Some(user) => Ok(user),
-            None => Err(AppError::NotFound),
+            None => Err(user_not_found()),
+        }
+    }
+
+    async fn get_user_entity_by_username(&self, username: &str) -> Result<User, AppError> {
+        let user_optional = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, password, email, created_at, updated_at, is_active, deleted_at
+            FROM users
+            WHERE username = $1 AND deleted_at IS NULL
+            "#,
+            username
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match user_optional {
+            Some(user) => Ok(user),
+            None => Err(user_not_found()),
+        }
+    }
+
+    async fn get_user_entity_by_id(&self, id: Uuid) -> Result<User, AppError> {
+        let user_optional = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, password, email, created_at, updated_at, is_active, deleted_at
+            FROM users
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match user_optional {
+            Some(user) => Ok(user),
+            None => Err(user_not_found()),
         }
     }
 
@@ -218,8 +473,8 @@ impl UserRepositoryTrait for UserRepository {
         id: Uuid,
         payload: UpdateUserDTO,
     ) -> Result<UserResponseDTO, AppError> {
-        if !self.check_if_id_exists(&id).await? {
-            return Err(AppError::NotFound);
+        if !Self::check_if_id_exists(&self.pool, &id).await? {
+            return Err(user_not_found());
         }
 
         let updated_user = sqlx::query_as!(
@@ -228,8 +483,9 @@ impl UserRepositoryTrait for UserRepository {
             UPDATE users
             SET username = COALESCE($2, username),
                 email = COALESCE($3, email),
-                password = COALESCE($4, password)
-            WHERE id = $1
+                password = COALESCE($4, password),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND deleted_at IS NULL
             RETURNING id, username, email
             "#,
             id,
@@ -244,40 +500,612 @@ impl UserRepositoryTrait for UserRepository {
     }
 
     async fn delete_user(&self, id: Uuid) -> Result<(), AppError> {
-        if !self.check_if_id_exists(&id).await? {
-            return Err(AppError::NotFound);
-        }
+        let mut transaction = self.pool.begin().await?;
 
         let query_result = sqlx::query!(
             r#"
-            DELETE FROM users
-            WHERE id = $1
+            UPDATE users
+            SET deleted_at = CURRENT_TIMESTAMP,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
             id
         )
-        .execute(&self.pool)
+        .execute(&mut *transaction)
         .await?;
 
         if query_result.rows_affected() == 0 {
-            return Err(AppError::InternalServerError(
-                "Failed to delete user".to_string(),
-            ));
+            return Err(user_not_found());
         }
 
+        sqlx::query!(
+            r#"
+            UPDATE sessions
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
         Ok(())
     }
 
-    async fn get_all_users(&self) -> Result<Vec<UserResponseDTO>, AppError> {
-        let users = sqlx::query_as!(
+    async fn restore_user(&self, id: Uuid) -> Result<UserResponseDTO, AppError> {
+        let user_optional = sqlx::query_as!(
             UserResponseDTO,
             r#"
-            SELECT id, username, email
+            UPDATE users
+            SET deleted_at = NULL,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND deleted_at IS NOT NULL
+            RETURNING id, username, email
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match user_optional {
+            Some(user) => Ok(user),
+            None => Err(deleted_user_not_found()),
+        }
+    }
+
+    async fn purge_user(&self, id: Uuid, new_owner_id: Option<Uuid>) -> Result<u64, AppError> {
+        let mut transaction = self.pool.begin().await?;
+
+        let is_deleted = sqlx::query_scalar!(
+            r#"
+            SELECT deleted_at IS NOT NULL AS "is_deleted!"
             FROM users
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        if is_deleted != Some(true) {
+            return Err(deleted_user_not_found());
+        }
+
+        let reassigned_stores = match new_owner_id {
+            Some(new_owner_id) => sqlx::query!(
+                r#"
+                UPDATE stores
+                SET owner_id = $2,
+                    updated_at = CURRENT_TIMESTAMP
+                WHERE owner_id = $1
+                "#,
+                id,
+                new_owner_id
+            )
+            .execute(&mut *transaction)
+            .await?
+            .rows_affected(),
+            None => 0,
+        };
+
+        let owns_stores = sqlx::query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM stores WHERE owner_id = $1) AS "owns_stores!""#,
+            id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        if owns_stores {
+            return Err(AppError::conflict(
+                "user_owns_stores",
+                "The user still owns stores; pass a new owner to reassign them",
+            ));
+        }
+
+        sqlx::query!("DELETE FROM users WHERE id = $1", id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(reassigned_stores)
+    }
+
+    async fn get_all_users(&self, query: &ListQuery) -> Result<Page<UserResponseDTO>, AppError> {
+        let sort = query.sort_key(&["username", "email", "created_at"], "username")?;
+        let offset = query.offset()?;
+        let email_pattern = query.email_pattern();
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM users u
+            WHERE u.deleted_at IS NULL
+              AND ($1::BOOL IS NULL OR u.is_active = $1)
+              AND ($2::TEXT IS NULL OR u.email ILIKE $2)
+              AND ($3::TEXT IS NULL OR EXISTS (SELECT 1
+                                               FROM user_roles ur
+                                                        JOIN roles r ON r.id = ur.role_id
+                                               WHERE ur.user_id = u.id
+                                                 AND r.name = $3))
             "#,
+            query.is_active,
+            email_pattern,
+            query.role
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let users = sqlx::query_as!(
+            UserResponseDTO,
+            r#"
+            SELECT u.id, u.username, u.email
+            FROM users u
+            WHERE u.deleted_at IS NULL
+              AND ($1::BOOL IS NULL OR u.is_active = $1)
+              AND ($2::TEXT IS NULL OR u.email ILIKE $2)
+              AND ($3::TEXT IS NULL OR EXISTS (SELECT 1
+                                               FROM user_roles ur
+                                                        JOIN roles r ON r.id = ur.role_id
+                                               WHERE ur.user_id = u.id
+                                                 AND r.name = $3))
+            ORDER BY CASE WHEN $4 = 'username' THEN u.username END,
+                     CASE WHEN $4 = '-username' THEN u.username END DESC,
+                     CASE WHEN $4 = 'email' THEN u.email END,
+                     CASE WHEN $4 = '-email' THEN u.email END DESC,
+                     CASE WHEN $4 = 'created_at' THEN u.created_at END,
+                     CASE WHEN $4 = '-created_at' THEN u.created_at END DESC,
+                     u.id
+            LIMIT $5 OFFSET $6
+            "#,
+            query.is_active,
+            email_pattern,
+            query.role,
+            sort,
+            query.limit(),
+            offset
         )
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(users)
+        Ok(Page::new(users, total, offset))
+    }
+
+    async fn deactivate_user(&self, id: Uuid) -> Result<EmployeeLifecycleEventDTO, AppError> {
+        let mut transaction = self.pool.begin().await?;
+
+        let occurred_at = sqlx::query_scalar!(
+            r#"
+            UPDATE users
+            SET is_active = FALSE,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND is_active AND deleted_at IS NULL
+            RETURNING updated_at
+            "#,
+            id
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let occurred_at = match occurred_at {
+            Some(occurred_at) => occurred_at,
+            None if Self::check_if_id_exists(&mut *transaction, &id).await? => {
+                return Err(AppError::conflict(
+                    "employee_already_inactive",
+                    "The employee is already deactivated",
+                ))
+            }
+            None => return Err(user_not_found()),
+        };
+
+        let revoked_sessions = sqlx::query!(
+            r#"
+            UPDATE sessions
+            SET revoked_at = CURRENT_TIMESTAMP
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+            id
+        )
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        let new_manager_id = sqlx::query_scalar!(
+            "DELETE FROM user_hierarchy WHERE user_id = $1 RETURNING reports_to",
+            id
+        )
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        let reassigned_reports = match new_manager_id {
+            Some(manager_id) => {
+                sqlx::query_scalar!(
+                    r#"
+                    UPDATE user_hierarchy
+                    SET reports_to = $2
+                    WHERE reports_to = $1
+                    RETURNING user_id
+                    "#,
+                    id,
+                    manager_id
+                )
+                .fetch_all(&mut *transaction)
+                .await?
+            }
+            None => {
+                sqlx::query_scalar!(
+                    "DELETE FROM user_hierarchy WHERE reports_to = $1 RETURNING user_id",
+                    id
+                )
+                .fetch_all(&mut *transaction)
+                .await?
+            }
+        };
+
+        let removed_store_memberships =
+            sqlx::query!("DELETE FROM store_users WHERE user_id = $1", id)
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected();
+
+        transaction.commit().await?;
+
+        Ok(EmployeeLifecycleEventDTO {
+            employee_id: id,
+            event: EmployeeLifecycleEventKind::Deactivated,
+            occurred_at,
+            revoked_sessions,
+            removed_store_memberships,
+            reassigned_reports,
+            new_manager_id,
+        })
+    }
+
+    async fn reactivate_user(&self, id: Uuid) -> Result<EmployeeLifecycleEventDTO, AppError> {
+        let occurred_at = sqlx::query_scalar!(
+            r#"
+            UPDATE users
+            SET is_active = TRUE,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND NOT is_active AND deleted_at IS NULL
+            RETURNING updated_at
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let occurred_at = match occurred_at {
+            Some(occurred_at) => occurred_at,
+            None if Self::check_if_id_exists(&self.pool, &id).await? => {
+                return Err(AppError::conflict(
+                    "employee_already_active",
+                    "The employee is already active",
+                ))
+            }
+            None => return Err(user_not_found()),
+        };
+
+        Ok(EmployeeLifecycleEventDTO {
+            employee_id: id,
+            event: EmployeeLifecycleEventKind::Reactivated,
+            occurred_at,
+            revoked_sessions: 0,
+            removed_store_memberships: 0,
+            reassigned_reports: Vec::new(),
+            new_manager_id: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{add_store_user, create_store, create_user};
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+
+    async fn create_session(pool: &PgPool, user_id: Uuid) {
+        sqlx::query(
+            "INSERT INTO sessions (user_id, refresh_token_hash, expires_at) \
+             VALUES ($1, md5(random()::text), CURRENT_TIMESTAMP + INTERVAL '1 day')",
+        )
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn active_sessions(pool: &PgPool, user_id: Uuid) -> i64 {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM sessions WHERE user_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn report_to(pool: &PgPool, user_id: Uuid, manager_id: Uuid) {
+        sqlx::query("INSERT INTO user_hierarchy (user_id, reports_to) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(manager_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn manager_of(pool: &PgPool, user_id: Uuid) -> Option<Uuid> {
+        sqlx::query_scalar("SELECT reports_to FROM user_hierarchy WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn store_memberships(pool: &PgPool, user_id: Uuid) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM store_users WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn is_active(pool: &PgPool, user_id: Uuid) -> bool {
+        sqlx::query_scalar("SELECT is_active FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    fn admin(username: &str) -> CreateUserDTO {
+        CreateUserDTO {
+            username: username.to_string(),
+            email: format!("{username}@example.com"),
+            password: "hashed".to_string(),
+        }
+    }
+
+    async fn holds_admin_role(pool: &PgPool, user_id: Uuid) -> bool {
+        sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM user_roles ur JOIN roles r ON r.id = ur.role_id \
+             WHERE ur.user_id = $1 AND r.name = 'admin' AND ur.store_id IS NULL)",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn first_admin_is_created_once(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+
+        let first = repo.create_first_admin(admin("root")).await.unwrap();
+        assert!(holds_admin_role(&pool, first.id).await);
+
+        let second = repo.create_first_admin(admin("root2")).await;
+        assert!(
+            matches!(&second, Err(AppError::Conflict(detail)) if detail.code == "admin_exists"),
+            "{second:?}"
+        );
+    }
+
+    #[sqlx::test]
+    async fn first_admin_can_be_created_again_once_the_admin_is_deactivated(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let first = repo.create_first_admin(admin("root")).await.unwrap();
+        sqlx::query("UPDATE users SET is_active = FALSE WHERE id = $1")
+            .bind(first.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let second = repo.create_first_admin(admin("root2")).await.unwrap();
+        assert!(holds_admin_role(&pool, second.id).await);
+    }
+
+    #[sqlx::test]
+    async fn first_admin_rolls_back_on_a_taken_username(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        create_user(&pool, "root").await;
+
+        let result = repo.create_first_admin(admin("root")).await;
+        assert!(
+            matches!(&result, Err(AppError::Conflict(detail)) if detail.code == "username_taken"),
+            "{result:?}"
+        );
+        let admins: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM user_roles ur JOIN roles r ON r.id = ur.role_id WHERE r.name = 'admin'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(admins, 0);
+    }
+
+    #[sqlx::test]
+    async fn deactivation_detaches_the_user_from_the_organisation(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        let carol = create_user(&pool, "carol").await;
+        let dave = create_user(&pool, "dave").await;
+        report_to(&pool, bob, alice).await;
+        report_to(&pool, carol, bob).await;
+        report_to(&pool, dave, bob).await;
+        create_session(&pool, bob).await;
+        create_session(&pool, bob).await;
+        create_session(&pool, alice).await;
+        let store_id = create_store(&pool, alice).await;
+        add_store_user(&pool, store_id, bob).await;
+        add_store_user(&pool, store_id, carol).await;
+
+        let event = repo.deactivate_user(bob).await.unwrap();
+
+        assert_eq!(event.revoked_sessions, 2);
+        assert_eq!(event.removed_store_memberships, 1);
+        assert_eq!(event.new_manager_id, Some(alice));
+        let mut reassigned = event.reassigned_reports.clone();
+        reassigned.sort();
+        let mut expected = vec![carol, dave];
+        expected.sort();
+        assert_eq!(reassigned, expected);
+
+        assert!(!is_active(&pool, bob).await);
+        assert_eq!(active_sessions(&pool, bob).await, 0);
+        assert_eq!(active_sessions(&pool, alice).await, 1);
+        assert_eq!(manager_of(&pool, bob).await, None);
+        assert_eq!(manager_of(&pool, carol).await, Some(alice));
+        assert_eq!(manager_of(&pool, dave).await, Some(alice));
+        assert_eq!(store_memberships(&pool, bob).await, 0);
+        assert_eq!(store_memberships(&pool, carol).await, 1);
+    }
+
+    #[sqlx::test]
+    async fn deactivating_a_top_level_user_makes_their_reports_top_level(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        report_to(&pool, bob, alice).await;
+
+        let event = repo.deactivate_user(alice).await.unwrap();
+
+        assert_eq!(event.new_manager_id, None);
+        assert_eq!(event.reassigned_reports, vec![bob]);
+        assert_eq!(manager_of(&pool, bob).await, None);
+    }
+
+    #[sqlx::test]
+    async fn deactivation_is_all_or_nothing(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        let carol = create_user(&pool, "carol").await;
+        report_to(&pool, bob, alice).await;
+        report_to(&pool, carol, bob).await;
+        create_session(&pool, bob).await;
+        let store_id = create_store(&pool, alice).await;
+        add_store_user(&pool, store_id, bob).await;
+        // Fail the last step of the deactivation, after every other write has run.
+        sqlx::raw_sql(
+            "CREATE FUNCTION refuse_delete() RETURNS trigger LANGUAGE plpgsql AS \
+             $$ BEGIN RAISE EXCEPTION 'refused'; END $$; \
+             CREATE TRIGGER refuse_store_users_delete BEFORE DELETE ON store_users \
+             FOR EACH ROW EXECUTE FUNCTION refuse_delete();",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert!(repo.deactivate_user(bob).await.is_err());
+
+        assert!(is_active(&pool, bob).await);
+        assert_eq!(active_sessions(&pool, bob).await, 1);
+        assert_eq!(manager_of(&pool, bob).await, Some(alice));
+        assert_eq!(manager_of(&pool, carol).await, Some(bob));
+        assert_eq!(store_memberships(&pool, bob).await, 1);
+    }
+
+    #[sqlx::test]
+    async fn deactivation_reports_inactive_and_unknown_users(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        repo.deactivate_user(alice).await.unwrap();
+
+        let again = repo.deactivate_user(alice).await;
+        assert!(
+            matches!(&again, Err(AppError::Conflict(detail)) if detail.code == "employee_already_inactive"),
+            "{again:?}"
+        );
+        assert!(matches!(
+            repo.deactivate_user(Uuid::new_v4()).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[sqlx::test]
+    async fn soft_delete_hides_the_user_and_revokes_their_sessions(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        create_session(&pool, alice).await;
+        create_session(&pool, alice).await;
+
+        repo.delete_user(alice).await.unwrap();
+
+        assert_eq!(active_sessions(&pool, alice).await, 0);
+        assert!(matches!(
+            repo.get_user_by_id(alice).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            repo.delete_user(alice).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[sqlx::test]
+    async fn restore_brings_a_deleted_user_back(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        assert!(matches!(
+            repo.restore_user(alice).await,
+            Err(AppError::NotFound(_))
+        ));
+        repo.delete_user(alice).await.unwrap();
+
+        let restored = repo.restore_user(alice).await.unwrap();
+
+        assert_eq!(restored.id, alice);
+        assert_eq!(repo.get_user_by_id(alice).await.unwrap().id, alice);
+    }
+
+    #[sqlx::test]
+    async fn purge_refuses_a_user_who_still_owns_stores(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        let store_id = create_store(&pool, alice).await;
+        repo.delete_user(alice).await.unwrap();
+
+        let refused = repo.purge_user(alice, None).await.unwrap_err();
+        assert!(
+            matches!(&refused, AppError::Conflict(detail) if detail.code == "user_owns_stores"),
+            "{refused:?}"
+        );
+        assert_eq!(refused.into_response().status(), StatusCode::CONFLICT);
+        assert!(repo.restore_user(alice).await.is_ok());
+        repo.delete_user(alice).await.unwrap();
+
+        assert_eq!(repo.purge_user(alice, Some(bob)).await.unwrap(), 1);
+
+        let owner_id: Uuid = sqlx::query_scalar("SELECT owner_id FROM stores WHERE store_id = $1")
+            .bind(store_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(owner_id, bob);
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE id = $1")
+            .bind(alice)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[sqlx::test]
+    async fn purge_only_removes_deleted_users(pool: PgPool) {
+        let repo = UserRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+
+        assert!(matches!(
+            repo.purge_user(alice, None).await,
+            Err(AppError::NotFound(_))
+        ));
+        assert!(repo.get_user_by_id(alice).await.is_ok());
     }
 }
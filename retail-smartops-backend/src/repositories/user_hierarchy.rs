@@ -0,0 +1,308 @@
+use crate::entities::user_hierarchy::UserHierarchy;
+use crate::errors::AppError;
+use crate::models::user_hierarchy::OrgChartEntryDTO;
+use axum::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Repository for managing the reporting hierarchy in the database.
+///
+/// Each user reports to at most one manager. Assignments that would make a user report to
+/// themselves, directly or through their own reports, are refused, so the hierarchy stays a forest.
+pub struct UserHierarchyRepository {
+    pool: PgPool,
+}
+
+impl UserHierarchyRepository {
+    /// Creates a new instance of `UserHierarchyRepository`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - A connection pool to the PostgreSQL database.
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Trait defining the operations for managing the reporting hierarchy.
+#[async_trait]
+pub trait UserHierarchyRepositoryTrait: Send + Sync {
+    /// Makes a user report to a manager, replacing their current manager if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    /// * `manager_id` - The UUID of the manager.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<UserHierarchy, AppError>` - Returns the reporting line, `AppError::Conflict` if the
+    ///   manager is the user or reports to them, or an `AppError` if an error occurs.
+    async fn set_manager(&self, user_id: Uuid, manager_id: Uuid)
+        -> Result<UserHierarchy, AppError>;
+
+    /// Removes the manager of a user, making them a top-level user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - Returns `Ok(())` if the reporting line was deleted, `AppError::NotFound`
+    ///   if the user has no manager, or an `AppError` if an error occurs.
+    async fn remove_manager(&self, user_id: Uuid) -> Result<(), AppError>;
+
+    /// Lists the users reporting directly to a manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `manager_id` - The UUID of the manager.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<OrgChartEntryDTO>, AppError>` - Returns the direct reports or an `AppError` if an error occurs.
+    async fn get_direct_reports(&self, manager_id: Uuid)
+        -> Result<Vec<OrgChartEntryDTO>, AppError>;
+
+    /// Lists every user reporting to a manager, directly or indirectly.
+    ///
+    /// # Arguments
+    ///
+    /// * `manager_id` - The UUID of the manager.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<OrgChartEntryDTO>, AppError>` - Returns the subtree below the manager, ordered by depth,
+    ///   or an `AppError` if an error occurs.
+    async fn get_subtree(&self, manager_id: Uuid) -> Result<Vec<OrgChartEntryDTO>, AppError>;
+
+    /// Lists the managers above a user, up to the top of the hierarchy.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<OrgChartEntryDTO>, AppError>` - Returns the management chain starting with the direct
+    ///   manager, or an `AppError` if an error occurs.
+    async fn get_management_chain(&self, user_id: Uuid) -> Result<Vec<OrgChartEntryDTO>, AppError>;
+}
+
+#[async_trait]
+impl UserHierarchyRepositoryTrait for UserHierarchyRepository {
+    async fn set_manager(
+        &self,
+        user_id: Uuid,
+        manager_id: Uuid,
+    ) -> Result<UserHierarchy, AppError> {
+        let mut transaction = self.pool.begin().await?;
+
+        // Serialize hierarchy changes so two concurrent assignments cannot close a loop together.
+        sqlx::query!("LOCK TABLE user_hierarchy IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *transaction)
+            .await?;
+
+        let creates_cycle = sqlx::query_scalar!(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT $2::uuid AS id
+                UNION
+                SELECT uh.reports_to
+                FROM user_hierarchy uh
+                JOIN chain c ON uh.user_id = c.id
+            )
+            SELECT EXISTS (SELECT 1 FROM chain WHERE id = $1) AS "creates_cycle!"
+            "#,
+            user_id,
+            manager_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        if creates_cycle {
+            return Err(AppError::conflict(
+                "reporting_cycle",
+                "The manager reports to this user, directly or indirectly",
+            ));
+        }
+
+        let user_hierarchy = sqlx::query_as!(
+            UserHierarchy,
+            r#"
+            INSERT INTO user_hierarchy (user_id, reports_to)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET reports_to = EXCLUDED.reports_to
+            RETURNING user_id, reports_to
+            "#,
+            user_id,
+            manager_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(user_hierarchy)
+    }
+
+    async fn remove_manager(&self, user_id: Uuid) -> Result<(), AppError> {
+        let query_result = sqlx::query!("DELETE FROM user_hierarchy WHERE user_id = $1", user_id)
+            .execute(&self.pool)
+            .await?;
+
+        if query_result.rows_affected() == 0 {
+            return Err(AppError::not_found(
+                "manager_not_found",
+                "The user has no manager",
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn get_direct_reports(
+        &self,
+        manager_id: Uuid,
+    ) -> Result<Vec<OrgChartEntryDTO>, AppError> {
+        let reports = sqlx::query_as!(
+            OrgChartEntryDTO,
+            r#"
+            SELECT u.id, u.username, u.email, uh.reports_to AS "reports_to?", 1 AS "depth!"
+            FROM user_hierarchy uh
+            JOIN users u ON u.id = uh.user_id
+            WHERE uh.reports_to = $1 AND u.deleted_at IS NULL
+            ORDER BY u.username
+            "#,
+            manager_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reports)
+    }
+
+    async fn get_subtree(&self, manager_id: Uuid) -> Result<Vec<OrgChartEntryDTO>, AppError> {
+        let subtree = sqlx::query_as!(
+            OrgChartEntryDTO,
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT user_id, reports_to, 1 AS depth
+                FROM user_hierarchy
+                WHERE reports_to = $1
+                UNION ALL
+                SELECT uh.user_id, uh.reports_to, s.depth + 1
+                FROM user_hierarchy uh
+                JOIN subtree s ON uh.reports_to = s.user_id
+            )
+            SELECT u.id, u.username, u.email, s.reports_to AS "reports_to?", s.depth AS "depth!"
+            FROM subtree s
+            JOIN users u ON u.id = s.user_id
+            WHERE u.deleted_at IS NULL
+            ORDER BY s.depth, u.username
+            "#,
+            manager_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(subtree)
+    }
+
+    async fn get_management_chain(&self, user_id: Uuid) -> Result<Vec<OrgChartEntryDTO>, AppError> {
+        let chain = sqlx::query_as!(
+            OrgChartEntryDTO,
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT reports_to AS manager_id, 1 AS depth
+                FROM user_hierarchy
+                WHERE user_id = $1
+                UNION ALL
+                SELECT uh.reports_to, c.depth + 1
+                FROM user_hierarchy uh
+                JOIN chain c ON uh.user_id = c.manager_id
+            )
+            SELECT u.id, u.username, u.email, uh.reports_to AS "reports_to?", c.depth AS "depth!"
+            FROM chain c
+            JOIN users u ON u.id = c.manager_id
+            LEFT JOIN user_hierarchy uh ON uh.user_id = c.manager_id
+            WHERE u.deleted_at IS NULL
+            ORDER BY c.depth
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_user;
+
+    fn is_cycle(result: Result<UserHierarchy, AppError>) -> bool {
+        matches!(result, Err(AppError::Conflict(detail)) if detail.code == "reporting_cycle")
+    }
+
+    #[sqlx::test]
+    async fn rejects_self_reference(pool: PgPool) {
+        let repo = UserHierarchyRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+
+        assert!(is_cycle(repo.set_manager(alice, alice).await));
+    }
+
+    #[sqlx::test]
+    async fn rejects_direct_and_indirect_cycles(pool: PgPool) {
+        let repo = UserHierarchyRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        let carol = create_user(&pool, "carol").await;
+        repo.set_manager(alice, bob).await.unwrap();
+        repo.set_manager(bob, carol).await.unwrap();
+
+        assert!(is_cycle(repo.set_manager(bob, alice).await));
+        assert!(is_cycle(repo.set_manager(carol, alice).await));
+        let chain = repo.get_management_chain(alice).await.unwrap();
+        assert_eq!(chain.len(), 2);
+    }
+
+    #[sqlx::test]
+    async fn replaces_manager(pool: PgPool) {
+        let repo = UserHierarchyRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        let carol = create_user(&pool, "carol").await;
+        repo.set_manager(alice, bob).await.unwrap();
+
+        let user_hierarchy = repo.set_manager(alice, carol).await.unwrap();
+
+        assert_eq!(user_hierarchy.reports_to, carol);
+        assert!(repo.get_direct_reports(bob).await.unwrap().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn rejects_cycle_closed_concurrently(pool: PgPool) {
+        let repo = std::sync::Arc::new(UserHierarchyRepository::new(pool.clone()));
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+
+        let (first, second) = tokio::join!(
+            tokio::spawn({
+                let repo = repo.clone();
+                async move { repo.set_manager(alice, bob).await }
+            }),
+            tokio::spawn({
+                let repo = repo.clone();
+                async move { repo.set_manager(bob, alice).await }
+            }),
+        );
+        let results = [first.unwrap(), second.unwrap()];
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+    }
+}
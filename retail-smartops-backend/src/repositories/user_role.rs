@@ -1,7 +1,10 @@
+use crate::entities::user_role::UserRole;
 use crate::errors::AppError;
-use crate::models::user_role::UserRoleResponseDTO;
+use crate::models::user_role::{
+    AssignUserRoleDTO, RoleMemberDTO, UserRoleDetailsDTO, UserRoleResponseDTO,
+};
 use axum::async_trait;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
 /// Repository for managing user roles in the database.
@@ -25,6 +28,7 @@ impl UserRoleRepository {
     ///
     /// * `user_id` - The UUID of the user.
     /// * `role_id` - The ID of the role.
+    /// * `store_id` - The ID of the store the role applies to, or `None` for a global assignment.
     ///
     /// # Returns
     ///
@@ -34,15 +38,17 @@ impl UserRoleRepository {
         &self,
         user_id: &Uuid,
         role_id: i32,
+        store_id: Option<i32>,
     ) -> Result<bool, AppError> {
         let user_role_count = sqlx::query!(
             r#"
             SELECT COUNT(*) as count
             FROM user_roles
-            WHERE user_id = $1 AND role_id = $2
+            WHERE user_id = $1 AND role_id = $2 AND store_id IS NOT DISTINCT FROM $3
             "#,
             user_id,
-            role_id
+            role_id,
+            store_id
         )
         .fetch_one(&self.pool)
         .await?
@@ -59,6 +65,39 @@ impl UserRoleRepository {
 
         Ok(count > 0)
     }
+
+    /// Fetches the roles held by a user, in every scope.
+    ///
+    /// Takes an executor so the role set can be read back inside a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - The pool or transaction connection to run the query on.
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<UserRoleDetailsDTO>, AppError>` - Returns the roles of the user or an `AppError` if an error occurs.
+    async fn fetch_roles_for_user<'e, E: PgExecutor<'e>>(
+        executor: E,
+        user_id: Uuid,
+    ) -> Result<Vec<UserRoleDetailsDTO>, AppError> {
+        let roles = sqlx::query_as!(
+            UserRoleDetailsDTO,
+            r#"
+            SELECT ur.role_id, r.name AS role_name, ur.store_id, ur.assigned_at
+            FROM user_roles ur
+            JOIN roles r ON r.id = ur.role_id
+            WHERE ur.user_id = $1
+            ORDER BY r.name, ur.store_id NULLS FIRST
+            "#,
+            user_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(roles)
+    }
 }
 
 /// Trait defining the operations for managing user roles.
@@ -70,6 +109,7 @@ pub trait UserRoleRepositoryTrait: Send + Sync {
     ///
     /// * `user_id` - The UUID of the user.
     /// * `role_id` - The ID of the role.
+    /// * `store_id` - The ID of the store the role applies to, or `None` for a global assignment.
     ///
     /// # Returns
     ///
@@ -78,23 +118,51 @@ pub trait UserRoleRepositoryTrait: Send + Sync {
         &self,
         user_id: uuid::Uuid,
         role_id: i32,
+        store_id: Option<i32>,
     ) -> Result<UserRoleResponseDTO, AppError>;
 
-    /// Updates a user role in the database.
+    /// Replaces one of a user's roles with another role in the same scope.
+    ///
+    /// The old assignment is removed and the new one added in a single transaction. Replacing
+    /// a role with one the user already holds in that scope simply drops the old assignment.
     ///
     /// # Arguments
     ///
     /// * `user_id` - The UUID of the user.
-    /// * `role_id` - The ID of the role.
+    /// * `role_id` - The ID of the role being replaced.
+    /// * `new_role_id` - The ID of the role replacing it.
+    /// * `store_id` - The ID of the store the role applies to, or `None` for a global assignment.
     ///
     /// # Returns
     ///
-    /// * `Result<UserRoleResponseDTO, AppError>` - Returns the updated user role or an `AppError` if an error occurs.
+    /// * `Result<Vec<UserRoleDetailsDTO>, AppError>` - Returns the resulting roles of the user, `AppError::NotFound`
+    ///   if the user does not hold `role_id` in that scope, or an `AppError` if an error occurs.
     async fn update_user_role(
         &self,
         user_id: uuid::Uuid,
         role_id: i32,
-    ) -> Result<UserRoleResponseDTO, AppError>;
+        new_role_id: i32,
+        store_id: Option<i32>,
+    ) -> Result<Vec<UserRoleDetailsDTO>, AppError>;
+
+    /// Sets the roles of a user to exactly the given assignments.
+    ///
+    /// Assignments missing from `roles` are removed and new ones added in a single transaction.
+    /// Assignments the user already holds are kept untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    /// * `roles` - The role assignments the user should hold.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<UserRoleDetailsDTO>, AppError>` - Returns the resulting roles of the user or an `AppError` if an error occurs.
+    async fn set_user_roles(
+        &self,
+        user_id: uuid::Uuid,
+        roles: &[AssignUserRoleDTO],
+    ) -> Result<Vec<UserRoleDetailsDTO>, AppError>;
 
     /// Deletes a user role from the database.
     ///
@@ -102,11 +170,40 @@ pub trait UserRoleRepositoryTrait: Send + Sync {
     ///
     /// * `user_id` - The UUID of the user.
     /// * `role_id` - The ID of the role.
+    /// * `store_id` - The ID of the store the role applies to, or `None` for a global assignment.
     ///
     /// # Returns
     ///
     /// * `Result<(), AppError>` - Returns `Ok(())` if the user role was deleted or an `AppError` if an error occurs.
-    async fn delete_user_role(&self, user_id: uuid::Uuid, role_id: i32) -> Result<(), AppError>;
+    async fn delete_user_role(
+        &self,
+        user_id: uuid::Uuid,
+        role_id: i32,
+        store_id: Option<i32>,
+    ) -> Result<(), AppError>;
+
+    /// Lists the roles held by a user, in every scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<UserRoleDetailsDTO>, AppError>` - Returns the roles of the user or an `AppError` if an error occurs.
+    async fn list_roles_for_user(&self, user_id: Uuid)
+        -> Result<Vec<UserRoleDetailsDTO>, AppError>;
+
+    /// Lists the users holding a role, in every scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<RoleMemberDTO>, AppError>` - Returns the members of the role or an `AppError` if an error occurs.
+    async fn list_users_for_role(&self, role_id: i32) -> Result<Vec<RoleMemberDTO>, AppError>;
 }
 
 #[async_trait]
@@ -117,6 +214,7 @@ impl UserRoleRepositoryTrait for UserRoleRepository {
     ///
     /// * `user_id` - The UUID of the user.
     /// * `role_id` - The ID of the role.
+    /// * `store_id` - The ID of the store the role applies to, or `None` for a global assignment.
     ///
     /// # Returns
     ///
@@ -125,61 +223,157 @@ impl UserRoleRepositoryTrait for UserRoleRepository {
         &self,
         user_id: Uuid,
         role_id: i32,
+        store_id: Option<i32>,
     ) -> Result<UserRoleResponseDTO, AppError> {
-        if self.check_if_user_role_exists(&user_id, role_id).await? {
-            return Err(AppError::Conflict);
+        if self
+            .check_if_user_role_exists(&user_id, role_id, store_id)
+            .await?
+        {
+            return Err(AppError::conflict(
+                "user_role_exists",
+                "The user already holds this role",
+            ));
         }
 
         let user_role = sqlx::query_as!(
-            UserRoleResponseDTO,
+            UserRole,
             r#"
-            INSERT INTO user_roles (user_id, role_id)
-            VALUES ($1, $2)
-            RETURNING user_id, role_id
+            INSERT INTO user_roles (user_id, role_id, store_id)
+            VALUES ($1, $2, $3)
+            RETURNING user_id, role_id, store_id, assigned_at
             "#,
             user_id,
-            role_id
+            role_id,
+            store_id
         )
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(user_role)
+        Ok(user_role.into())
     }
 
-    /// Updates a user role in the database.
+    /// Replaces one of a user's roles with another role in the same scope.
+    ///
+    /// The old assignment is removed and the new one added in a single transaction. Replacing
+    /// a role with one the user already holds in that scope simply drops the old assignment.
     ///
     /// # Arguments
     ///
     /// * `user_id` - The UUID of the user.
-    /// * `role_id` - The ID of the role.
+    /// * `role_id` - The ID of the role being replaced.
+    /// * `new_role_id` - The ID of the role replacing it.
+    /// * `store_id` - The ID of the store the role applies to, or `None` for a global assignment.
     ///
     /// # Returns
     ///
-    /// * `Result<UserRoleResponseDTO, AppError>` - Returns the updated user role or an `AppError` if an error occurs.
+    /// * `Result<Vec<UserRoleDetailsDTO>, AppError>` - Returns the resulting roles of the user, `AppError::NotFound`
+    ///   if the user does not hold `role_id` in that scope, or an `AppError` if an error occurs.
     async fn update_user_role(
         &self,
         user_id: Uuid,
         role_id: i32,
-    ) -> Result<UserRoleResponseDTO, AppError> {
-        if !self.check_if_user_role_exists(&user_id, role_id).await? {
-            return Err(AppError::NotFound);
-        }
+        new_role_id: i32,
+        store_id: Option<i32>,
+    ) -> Result<Vec<UserRoleDetailsDTO>, AppError> {
+        let mut transaction = self.pool.begin().await?;
 
-        let user_role = sqlx::query_as!(
-            UserRoleResponseDTO,
+        let query_result = sqlx::query!(
             r#"
-            UPDATE user_roles
-            SET role_id = $1
-            WHERE user_id = $2
-            RETURNING user_id, role_id
+            DELETE FROM user_roles
+            WHERE user_id = $1 AND role_id = $2 AND store_id IS NOT DISTINCT FROM $3
             "#,
+            user_id,
             role_id,
-            user_id
+            store_id
         )
-        .fetch_one(&self.pool)
+        .execute(&mut *transaction)
         .await?;
 
-        Ok(user_role)
+        if query_result.rows_affected() == 0 {
+            return Err(AppError::not_found(
+                "user_role_not_found",
+                "The user does not hold this role",
+            ));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_roles (user_id, role_id, store_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            new_role_id,
+            store_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let roles = Self::fetch_roles_for_user(&mut *transaction, user_id).await?;
+        transaction.commit().await?;
+
+        Ok(roles)
+    }
+
+    /// Sets the roles of a user to exactly the given assignments.
+    ///
+    /// Assignments missing from `roles` are removed and new ones added in a single transaction.
+    /// Assignments the user already holds are kept untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    /// * `roles` - The role assignments the user should hold.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<UserRoleDetailsDTO>, AppError>` - Returns the resulting roles of the user or an `AppError` if an error occurs.
+    async fn set_user_roles(
+        &self,
+        user_id: Uuid,
+        roles: &[AssignUserRoleDTO],
+    ) -> Result<Vec<UserRoleDetailsDTO>, AppError> {
+        let mut transaction = self.pool.begin().await?;
+
+        let current_roles = Self::fetch_roles_for_user(&mut *transaction, user_id).await?;
+
+        for current_role in current_roles.iter().filter(|current_role| {
+            !roles.iter().any(|role| {
+                role.role_id == current_role.role_id && role.store_id == current_role.store_id
+            })
+        }) {
+            sqlx::query!(
+                r#"
+                DELETE FROM user_roles
+                WHERE user_id = $1 AND role_id = $2 AND store_id IS NOT DISTINCT FROM $3
+                "#,
+                user_id,
+                current_role.role_id,
+                current_role.store_id
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        for role in roles {
+            sqlx::query!(
+                r#"
+                INSERT INTO user_roles (user_id, role_id, store_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT DO NOTHING
+                "#,
+                user_id,
+                role.role_id,
+                role.store_id
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let roles = Self::fetch_roles_for_user(&mut *transaction, user_id).await?;
+        transaction.commit().await?;
+
+        Ok(roles)
     }
 
     /// Deletes a user role from the database.
@@ -188,22 +382,35 @@ impl UserRoleRepositoryTrait for UserRoleRepository {
     ///
     /// * `user_id` - The UUID of the user.
     /// * `role_id` - The ID of the role.
+    /// * `store_id` - The ID of the store the role applies to, or `None` for a global assignment.
     ///
     /// # Returns
     ///
     /// * `Result<(), AppError>` - Returns `Ok(())` if the user role was deleted or an `AppError` if an error occurs.
-    async fn delete_user_role(&self, user_id: Uuid, role_id: i32) -> Result<(), AppError> {
-        if !self.check_if_user_role_exists(&user_id, role_id).await? {
-            return Err(AppError::NotFound);
+    async fn delete_user_role(
+        &self,
+        user_id: Uuid,
+        role_id: i32,
+        store_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        if !self
+            .check_if_user_role_exists(&user_id, role_id, store_id)
+            .await?
+        {
+            return Err(AppError::not_found(
+                "user_role_not_found",
+                "The user does not hold this role",
+            ));
         }
 
         let query_result = sqlx::query!(
             r#"
             DELETE FROM user_roles
-            WHERE user_id = $1 AND role_id = $2
+            WHERE user_id = $1 AND role_id = $2 AND store_id IS NOT DISTINCT FROM $3
             "#,
             user_id,
-            role_id
+            role_id,
+            store_id
         )
         .execute(&self.pool)
         .await?;
@@ -216,4 +423,172 @@ impl UserRoleRepositoryTrait for UserRoleRepository {
 
         Ok(())
     }
+
+    /// Lists the roles held by a user, in every scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<UserRoleDetailsDTO>, AppError>` - Returns the roles of the user or an `AppError` if an error occurs.
+    async fn list_roles_for_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserRoleDetailsDTO>, AppError> {
+        Self::fetch_roles_for_user(&self.pool, user_id).await
+    }
+
+    /// Lists the users holding a role, in every scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<RoleMemberDTO>, AppError>` - Returns the members of the role or an `AppError` if an error occurs.
+    async fn list_users_for_role(&self, role_id: i32) -> Result<Vec<RoleMemberDTO>, AppError> {
+        let members = sqlx::query_as!(
+            RoleMemberDTO,
+            r#"
+            SELECT ur.user_id, u.username, u.email, ur.store_id, ur.assigned_at
+            FROM user_roles ur
+            JOIN users u ON u.id = ur.user_id
+            WHERE ur.role_id = $1 AND u.deleted_at IS NULL
+            ORDER BY u.username, ur.store_id NULLS FIRST
+            "#,
+            role_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{create_role, create_store, create_user, grant_role};
+
+    fn scoped(roles: &[UserRoleDetailsDTO]) -> Vec<(i32, Option<i32>)> {
+        let mut scoped: Vec<_> = roles
+            .iter()
+            .map(|role| (role.role_id, role.store_id))
+            .collect();
+        scoped.sort();
+        scoped
+    }
+
+    fn assignment(role_id: i32, store_id: Option<i32>) -> AssignUserRoleDTO {
+        AssignUserRoleDTO { role_id, store_id }
+    }
+
+    #[sqlx::test]
+    async fn replaces_one_role_in_its_scope(pool: PgPool) {
+        let repo = UserRoleRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let store_id = create_store(&pool, alice).await;
+        let clerk = create_role(&pool, "clerk", &[]).await;
+        let cashier = create_role(&pool, "cashier", &[]).await;
+        grant_role(&pool, alice, clerk, None).await;
+        grant_role(&pool, alice, clerk, Some(store_id)).await;
+
+        let roles = repo
+            .update_user_role(alice, clerk, cashier, Some(store_id))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            scoped(&roles),
+            vec![(clerk, None), (cashier, Some(store_id))]
+        );
+    }
+
+    #[sqlx::test]
+    async fn replacing_a_role_not_held_changes_nothing(pool: PgPool) {
+        let repo = UserRoleRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let clerk = create_role(&pool, "clerk", &[]).await;
+        let cashier = create_role(&pool, "cashier", &[]).await;
+        grant_role(&pool, alice, clerk, None).await;
+
+        let result = repo.update_user_role(alice, cashier, clerk, None).await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+        assert_eq!(
+            scoped(&repo.list_roles_for_user(alice).await.unwrap()),
+            vec![(clerk, None)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn replacing_with_a_missing_role_rolls_back(pool: PgPool) {
+        let repo = UserRoleRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let clerk = create_role(&pool, "clerk", &[]).await;
+        grant_role(&pool, alice, clerk, None).await;
+
+        let result = repo.update_user_role(alice, clerk, i32::MAX, None).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            scoped(&repo.list_roles_for_user(alice).await.unwrap()),
+            vec![(clerk, None)]
+        );
+    }
+
+    #[sqlx::test]
+    async fn sets_roles_to_exactly_the_given_assignments(pool: PgPool) {
+        let repo = UserRoleRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let clerk = create_role(&pool, "clerk", &[]).await;
+        let cashier = create_role(&pool, "cashier", &[]).await;
+        let auditor = create_role(&pool, "auditor", &[]).await;
+        grant_role(&pool, alice, clerk, None).await;
+        grant_role(&pool, alice, cashier, None).await;
+        let kept_since = repo.list_roles_for_user(alice).await.unwrap();
+        let kept_since = kept_since
+            .iter()
+            .find(|role| role.role_id == clerk)
+            .unwrap()
+            .assigned_at;
+
+        let roles = repo
+            .set_user_roles(alice, &[assignment(clerk, None), assignment(auditor, None)])
+            .await
+            .unwrap();
+
+        assert_eq!(scoped(&roles), vec![(clerk, None), (auditor, None)]);
+        let kept = roles.iter().find(|role| role.role_id == clerk).unwrap();
+        assert_eq!(kept.assigned_at, kept_since);
+    }
+
+    #[sqlx::test]
+    async fn setting_roles_with_a_missing_role_keeps_the_previous_set(pool: PgPool) {
+        let repo = UserRoleRepository::new(pool.clone());
+        let alice = create_user(&pool, "alice").await;
+        let clerk = create_role(&pool, "clerk", &[]).await;
+        let cashier = create_role(&pool, "cashier", &[]).await;
+        grant_role(&pool, alice, clerk, None).await;
+
+        let result = repo
+            .set_user_roles(
+                alice,
+                &[assignment(cashier, None), assignment(i32::MAX, None)],
+            )
+            .await;
+
+        assert!(
+            matches!(&result, Err(AppError::DbError(sqlx::Error::Database(e)))
+                if e.code().as_deref() == Some("23503")),
+            "{result:?}"
+        );
+        assert_eq!(
+            scoped(&repo.list_roles_for_user(alice).await.unwrap()),
+            vec![(clerk, None)]
+        );
+    }
 }
@@ -0,0 +1,72 @@
+use axum::extract::Request;
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// The header a request ID is read from and echoed in.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// The longest request ID accepted from a client. Longer IDs are replaced by a generated one.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Returns the ID of the request being handled by the current task, if any.
+///
+/// # Returns
+///
+/// An `Option<String>` with the request ID, or `None` outside of `propagate_request_id`.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|request_id| request_id.clone()).ok()
+}
+
+/// Middleware that makes the ID of a request available to the code handling it.
+///
+/// The ID is taken from the `X-Request-Id` header when the client sent a usable one, otherwise
+/// a new UUID is generated and written to the request headers, so inner layers such as the
+/// tracing span always find it there. Error responses built while handling the request include
+/// it in their body, and every response echoes it in the `X-Request-Id` header.
+///
+/// # Returns
+///
+/// The `Response` of the inner service.
+pub async fn propagate_request_id(mut request: Request, next: Next) -> Response {
+    let request_id = match request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_REQUEST_ID_LENGTH)
+    {
+        Some(request_id) => request_id.to_owned(),
+        None => {
+            let request_id = Uuid::new_v4().to_string();
+            request.headers_mut().insert(
+                REQUEST_ID_HEADER,
+                HeaderValue::from_str(&request_id).expect("a UUID is a valid header value"),
+            );
+            request_id
+        }
+    };
+
+    let header_value = HeaderValue::from_str(&request_id).expect("checked to be a valid header");
+    let mut response = REQUEST_ID.scope(request_id, next.run(request)).await;
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, header_value);
+    response
+}
+
+/// Reads the request ID set by `propagate_request_id` from the request headers.
+///
+/// # Returns
+///
+/// An `Option<&str>` with the request ID, or `None` if the middleware has not run.
+pub fn request_id_of<B>(request: &axum::http::Request<B>) -> Option<&str> {
+    request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+}
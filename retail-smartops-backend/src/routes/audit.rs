@@ -0,0 +1,16 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::audit::get_audit_events;
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::get;
+use axum::Router;
+
+pub fn create_audit_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/audit", get(get_audit_events))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("audit", Action::Read),
+        ))
+        .with_state(app_state)
+}
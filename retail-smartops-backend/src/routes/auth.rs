@@ -0,0 +1,18 @@
+use crate::handlers::auth::{current_user, login, logout, refresh};
+use crate::AppState;
+use axum::routing::{get, post};
+use axum::Router;
+
+pub fn create_auth_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/auth/login", post(login))
+        .route("/auth/refresh", post(refresh))
+        .route("/auth/logout", post(logout))
+        .with_state(app_state)
+}
+
+pub fn create_current_user_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/auth/me", get(current_user))
+        .with_state(app_state)
+}
@@ -0,0 +1,31 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::employee::{
+    deactivate_employee, get_employee, reactivate_employee, update_employee,
+};
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, patch, post};
+use axum::Router;
+
+pub fn create_employee_routes(app_state: AppState) -> Router {
+    let read_routes = Router::new()
+        .route("/employees/:id", get(get_employee))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Read),
+        ));
+
+    let update_routes = Router::new()
+        .route("/employees/:id", patch(update_employee))
+        .route("/employees/:id/deactivate", post(deactivate_employee))
+        .route("/employees/:id/reactivate", post(reactivate_employee))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Update),
+        ));
+
+    Router::new()
+        .merge(read_routes)
+        .merge(update_routes)
+        .with_state(app_state)
+}
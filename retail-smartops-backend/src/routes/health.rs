@@ -1,4 +1,4 @@
-use crate::handlers::health::health_check;
+use crate::handlers::health::{health_check, liveness, readiness};
 use crate::AppState;
 use axum::routing::get;
 use axum::Router;
@@ -6,5 +6,7 @@ use axum::Router;
 pub fn create_health_routes(app_state: AppState) -> Router {
     Router::new()
         .route("/health", get(health_check))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
         .with_state(app_state)
 }
@@ -0,0 +1,16 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::login_lockout::unlock_login;
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::post;
+use axum::Router;
+
+pub fn create_login_lockout_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/login-lockouts/unlock", post(unlock_login))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("login_lockouts", Action::Delete),
+        ))
+        .with_state(app_state)
+}
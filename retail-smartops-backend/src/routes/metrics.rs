@@ -0,0 +1,10 @@
+use crate::handlers::metrics::metrics;
+use crate::AppState;
+use axum::routing::get;
+use axum::Router;
+
+pub fn create_metrics_routes(app_state: AppState) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(app_state)
+}
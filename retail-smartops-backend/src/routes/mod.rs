@@ -1,13 +1,29 @@
+use crate::auth::extractor::require_auth;
+use crate::errors::AppError;
+use crate::metrics::track_http_metrics;
+use crate::request_id::{propagate_request_id, request_id_of};
 use crate::AppState;
 use axum::extract::{MatchedPath, Request};
-use axum::response::Response;
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
 use axum::Router;
 use std::time::Duration;
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 use tracing::{info_span, Span};
 
+mod audit;
+mod auth;
+mod employee;
 mod health;
+mod login_lockout;
+mod metrics;
+mod permission;
+mod role;
+mod store;
+mod user;
+mod user_hierarchy;
+mod user_role;
 
 /// Creates the application routes and sets up tracing for HTTP requests.
 ///
@@ -15,6 +31,16 @@ mod health;
 /// It creates spans for each request, logs the start of the request, the response generation time,
 /// and other tracing events.
 ///
+/// Every request runs inside `propagate_request_id`, the outermost layer, so its span, its
+/// error body and its response headers carry its request ID. Requests are counted by
+/// `track_http_metrics`. The metrics are served at `/metrics`, outside of `/api`.
+///
+/// Routes that need a caller identity are merged into a protected router wrapped with the
+/// `require_auth` middleware.
+///
+/// Unknown paths get a `route_not_found` problem body, like every other error, rather than
+/// axum's empty 404.
+///
 /// # Returns
 ///
 /// A `Router` instance with the configured routes and tracing layer.
@@ -23,12 +49,13 @@ pub fn create_app_routes(app_state: AppState) -> Router {
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(|request: &Request<_>| {
-                    // Create a span for the HTTP request with the method and matched path.
+                    // Create a span for the HTTP request with the method, matched path and request ID.
                     let matched_path = request
                         .extensions()
                         .get::<MatchedPath>()
                         .map(MatchedPath::as_str);
-                    info_span!("http_request", method= ?request.method(), matched_path)
+                    let request_id = request_id_of(request);
+                    info_span!("http_request", method= ?request.method(), matched_path, request_id)
                 })
                 .on_request(|_request: &Request<_>, _span: &Span| tracing::info!("request started"))
                 .on_response(|_response: &Response<_>, latency: Duration, _span: &Span| {
@@ -40,7 +67,64 @@ pub fn create_app_routes(app_state: AppState) -> Router {
         )
         .into_inner();
 
+    let protected_routes = Router::new()
+        .merge(audit::create_audit_routes(app_state.clone()))
+        .merge(auth::create_current_user_routes(app_state.clone()))
+        .merge(employee::create_employee_routes(app_state.clone()))
+        .merge(login_lockout::create_login_lockout_routes(
+            app_state.clone(),
+        ))
+        .merge(permission::create_permission_routes(app_state.clone()))
+        .merge(role::create_role_routes(app_state.clone()))
+        .merge(store::create_store_routes(app_state.clone()))
+        .merge(user::create_user_routes(app_state.clone()))
+        .merge(user_hierarchy::create_user_hierarchy_routes(
+            app_state.clone(),
+        ))
+        .merge(user_role::create_user_role_routes(app_state.clone()))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            require_auth,
+        ));
+
+    let api_routes = Router::new()
+        .merge(health::create_health_routes(app_state.clone()))
+        .merge(auth::create_auth_routes(app_state.clone()))
+        .merge(protected_routes);
+
     Router::new()
-        .nest("/api", health::create_health_routes(app_state.clone()))
+        .nest("/api", api_routes)
+        .merge(metrics::create_metrics_routes(app_state.clone()))
+        .fallback(|| async {
+            AppError::not_found("route_not_found", "No such route").into_response()
+        })
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            track_http_metrics,
+        ))
         .layer(services)
+        .layer(middleware::from_fn(propagate_request_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::PROBLEM_JSON;
+    use crate::request_id::REQUEST_ID_HEADER;
+    use crate::test_support::{app, json_body, send};
+    use axum::http::{header, Method, StatusCode};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn unknown_routes_get_a_problem_body(pool: PgPool) {
+        let app = app(&pool);
+
+        for uri in ["/api/nope", "/nope"] {
+            let response = send(&app, Method::GET, uri, None, None).await;
+
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], PROBLEM_JSON);
+            assert!(response.headers().contains_key(REQUEST_ID_HEADER));
+            assert_eq!(json_body(response).await["code"], "route_not_found");
+        }
+    }
 }
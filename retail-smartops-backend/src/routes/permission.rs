@@ -0,0 +1,66 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::permission::{
+    create_permission, delete_permission, get_permission, get_permissions, get_role_permissions,
+    grant_role_permission, revoke_role_permission, update_permission,
+};
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, patch, post};
+use axum::Router;
+
+pub fn create_permission_routes(app_state: AppState) -> Router {
+    let read_routes = Router::new()
+        .route("/permissions", get(get_permissions))
+        .route("/permissions/:id", get(get_permission))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("permissions", Action::Read),
+        ));
+
+    let write_routes = Router::new()
+        .route("/permissions", post(create_permission))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("permissions", Action::Write),
+        ));
+
+    let update_routes = Router::new()
+        .route("/permissions/:id", patch(update_permission))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("permissions", Action::Update),
+        ));
+
+    let delete_routes = Router::new()
+        .route("/permissions/:id", delete(delete_permission))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("permissions", Action::Delete),
+        ));
+
+    let role_read_routes = Router::new()
+        .route("/roles/:id/permissions", get(get_role_permissions))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("roles", Action::Read),
+        ));
+
+    let role_update_routes = Router::new()
+        .route(
+            "/roles/:id/permissions/:permission_id",
+            post(grant_role_permission).delete(revoke_role_permission),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("roles", Action::Update),
+        ));
+
+    Router::new()
+        .merge(read_routes)
+        .merge(write_routes)
+        .merge(update_routes)
+        .merge(delete_routes)
+        .merge(role_read_routes)
+        .merge(role_update_routes)
+        .with_state(app_state)
+}
@@ -0,0 +1,44 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::role::{create_role, delete_role, get_role, get_roles, update_role};
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, patch, post};
+use axum::Router;
+
+pub fn create_role_routes(app_state: AppState) -> Router {
+    let read_routes = Router::new()
+        .route("/roles", get(get_roles))
+        .route("/roles/:id", get(get_role))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("roles", Action::Read),
+        ));
+
+    let write_routes = Router::new()
+        .route("/roles", post(create_role))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("roles", Action::Write),
+        ));
+
+    let update_routes = Router::new()
+        .route("/roles/:id", patch(update_role))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("roles", Action::Update),
+        ));
+
+    let delete_routes = Router::new()
+        .route("/roles/:id", delete(delete_role))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("roles", Action::Delete),
+        ));
+
+    Router::new()
+        .merge(read_routes)
+        .merge(write_routes)
+        .merge(update_routes)
+        .merge(delete_routes)
+        .with_state(app_state)
+}
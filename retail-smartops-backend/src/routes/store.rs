@@ -0,0 +1,138 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::store::{
+    add_store_user, create_store, delete_store, get_store, get_store_staff, get_stores,
+    get_user_stores, remove_store_user, update_store,
+};
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, patch, post};
+use axum::Router;
+
+/// Creates the store routes.
+///
+/// Routes addressing a single store use the `:store_id` parameter, so roles granted in that
+/// store apply to them alongside global roles. Managing the staff of a store requires the
+/// `stores` update permission. Changing the owner of a store requires it through a global role.
+pub fn create_store_routes(app_state: AppState) -> Router {
+    let read_routes = Router::new()
+        .route("/stores", get(get_stores))
+        .route("/stores/:store_id", get(get_store))
+        .route("/stores/:store_id/users", get(get_store_staff))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("stores", Action::Read),
+        ));
+
+    let write_routes = Router::new()
+        .route("/stores", post(create_store))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("stores", Action::Write),
+        ));
+
+    let update_routes = Router::new()
+        .route("/stores/:store_id", patch(update_store))
+        .route("/stores/:store_id/users", post(add_store_user))
+        .route(
+            "/stores/:store_id/users/:user_id",
+            delete(remove_store_user),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("stores", Action::Update),
+        ));
+
+    let delete_routes = Router::new()
+        .route("/stores/:store_id", delete(delete_store))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("stores", Action::Delete),
+        ));
+
+    let user_read_routes = Router::new()
+        .route("/users/:id/stores", get(get_user_stores))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Read),
+        ));
+
+    Router::new()
+        .merge(read_routes)
+        .merge(write_routes)
+        .merge(update_routes)
+        .merge(delete_routes)
+        .merge(user_read_routes)
+        .with_state(app_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{
+        add_store_user, app, create_role, create_store, create_user, grant_role, json_body, send,
+    };
+    use axum::http::{Method, StatusCode};
+    use serde_json::json;
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn only_global_store_updaters_can_change_the_owner(pool: PgPool) {
+        let app = app(&pool);
+        let editor = create_role(&pool, "editor", &[("stores", true, false, false, true)]).await;
+        let owner = create_user(&pool, "owner").await;
+        let store_id = create_store(&pool, owner).await;
+        let manager = create_user(&pool, "manager").await;
+        add_store_user(&pool, store_id, manager).await;
+        grant_role(&pool, manager, editor, Some(store_id)).await;
+        let regional = create_user(&pool, "regional").await;
+        grant_role(&pool, regional, editor, None).await;
+        let uri = format!("/api/stores/{store_id}");
+
+        let renamed = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(manager),
+            Some(json!({ "name": "Downtown" })),
+        )
+        .await;
+        assert_eq!(renamed.status(), StatusCode::OK);
+
+        let unchanged_owner = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(manager),
+            Some(json!({ "owner_id": owner })),
+        )
+        .await;
+        assert_eq!(unchanged_owner.status(), StatusCode::OK);
+
+        let taken_over = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(manager),
+            Some(json!({ "owner_id": manager })),
+        )
+        .await;
+        assert_eq!(taken_over.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            json_body(taken_over).await["code"],
+            "owner_change_requires_global_permission"
+        );
+
+        let transferred = send(
+            &app,
+            Method::PATCH,
+            &uri,
+            Some(regional),
+            Some(json!({ "owner_id": manager })),
+        )
+        .await;
+        assert_eq!(transferred.status(), StatusCode::OK);
+        assert_eq!(
+            json_body(transferred).await["owner_id"],
+            manager.to_string()
+        );
+    }
+}
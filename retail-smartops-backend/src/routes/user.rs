@@ -0,0 +1,55 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::user::{
+    create_user, delete_user, get_user, get_users, purge_user, restore_user, update_user,
+};
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{delete, get, patch, post};
+use axum::Router;
+
+pub fn create_user_routes(app_state: AppState) -> Router {
+    let read_routes = Router::new()
+        .route("/users", get(get_users))
+        .route("/users/:id", get(get_user))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Read),
+        ));
+
+    let write_routes = Router::new()
+        .route("/users", post(create_user))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Write),
+        ));
+
+    let update_routes = Router::new()
+        .route("/users/:id", patch(update_user))
+        .route("/users/:id/restore", post(restore_user))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Update),
+        ));
+
+    let delete_routes = Router::new()
+        .route("/users/:id", delete(delete_user))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Delete),
+        ));
+
+    let purge_routes = Router::new()
+        .route("/users/:id/purge", delete(purge_user))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("user_purge", Action::Delete),
+        ));
+
+    Router::new()
+        .merge(read_routes)
+        .merge(write_routes)
+        .merge(update_routes)
+        .merge(delete_routes)
+        .merge(purge_routes)
+        .with_state(app_state)
+}
@@ -0,0 +1,38 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::user_hierarchy::{
+    get_direct_reports, get_management_chain, get_subordinates, my_team, remove_manager,
+    set_manager,
+};
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, put};
+use axum::Router;
+
+pub fn create_user_hierarchy_routes(app_state: AppState) -> Router {
+    let team_routes = Router::new().route("/auth/me/team", get(my_team));
+
+    let read_routes = Router::new()
+        .route("/users/:id/reports", get(get_direct_reports))
+        .route("/users/:id/subordinates", get(get_subordinates))
+        .route("/users/:id/managers", get(get_management_chain))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Read),
+        ));
+
+    let update_routes = Router::new()
+        .route(
+            "/users/:id/manager",
+            put(set_manager).delete(remove_manager),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Update),
+        ));
+
+    Router::new()
+        .merge(team_routes)
+        .merge(read_routes)
+        .merge(update_routes)
+        .with_state(app_state)
+}
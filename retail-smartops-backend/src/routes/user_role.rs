@@ -0,0 +1,102 @@
+use crate::auth::permission::{require_permission, Action};
+use crate::handlers::user_role::{
+    assign_user_role, get_role_members, get_user_roles, replace_user_role, revoke_user_role,
+    set_user_roles,
+};
+use crate::AppState;
+use axum::middleware::from_fn_with_state;
+use axum::routing::{get, post, put};
+use axum::Router;
+
+pub fn create_user_role_routes(app_state: AppState) -> Router {
+    let user_read_routes = Router::new()
+        .route("/users/:id/roles", get(get_user_roles))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("users", Action::Read),
+        ));
+
+    // These paths have no `:store_id`, so only global roles pass the guard: roles held in a
+    // store do not let their holder assign roles, not even in that store.
+    let user_role_update_routes = Router::new()
+        .route(
+            "/users/:id/roles",
+            post(assign_user_role).put(set_user_roles),
+        )
+        .route(
+            "/users/:id/roles/:role_id",
+            put(replace_user_role).delete(revoke_user_role),
+        )
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("user_roles", Action::Update),
+        ));
+
+    let role_read_routes = Router::new()
+        .route("/roles/:id/users", get(get_role_members))
+        .route_layer(from_fn_with_state(
+            app_state.clone(),
+            require_permission("roles", Action::Read),
+        ));
+
+    Router::new()
+        .merge(user_read_routes)
+        .merge(user_role_update_routes)
+        .merge(role_read_routes)
+        .with_state(app_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{
+        add_store_user, app, create_role, create_store, create_user, grant_role, send,
+    };
+    use axum::http::{Method, StatusCode};
+    use serde_json::json;
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn assigning_roles_requires_a_global_user_roles_permission(pool: PgPool) {
+        let app = app(&pool);
+        let support = create_role(
+            &pool,
+            "support",
+            &[
+                ("users", true, false, false, false),
+                ("user_roles", true, true, false, true),
+            ],
+        )
+        .await;
+        let clerk = create_role(&pool, "clerk", &[("users", true, false, false, false)]).await;
+        let owner = create_user(&pool, "owner").await;
+        let store_id = create_store(&pool, owner).await;
+        let store_manager = create_user(&pool, "store_manager").await;
+        add_store_user(&pool, store_id, store_manager).await;
+        grant_role(&pool, store_manager, support, Some(store_id)).await;
+        let global_support = create_user(&pool, "global_support").await;
+        grant_role(&pool, global_support, support, None).await;
+        let bob = create_user(&pool, "bob").await;
+        let uri = format!("/api/users/{bob}/roles");
+        let payload = json!({ "role_id": clerk, "store_id": store_id });
+
+        let store_scoped = send(
+            &app,
+            Method::POST,
+            &uri,
+            Some(store_manager),
+            Some(payload.clone()),
+        )
+        .await;
+        assert_eq!(store_scoped.status(), StatusCode::FORBIDDEN);
+
+        let global = send(
+            &app,
+            Method::POST,
+            &uri,
+            Some(global_support),
+            Some(payload),
+        )
+        .await;
+        assert_eq!(global.status(), StatusCode::CREATED);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::auth::extractor::AuditContext;
+use crate::models::audit::{AuditAction, AuditEntity, AuditQuery, CreateAuditEventDTO};
+use crate::repositories::RepositoryContainer;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// The value recorded instead of a secret that changed.
+const REDACTED: &str = "********";
+
+/// A change to record in the audit log.
+///
+/// Snapshots are serialized from the DTOs the services already hold, so secrets such as
+/// password hashes never reach the log. When both snapshots are objects, only the fields that
+/// differ are kept.
+///
+/// # Fields
+///
+/// * `action` - The kind of change.
+/// * `entity` - The kind of record that changed.
+/// * `entity_id` - The identifier of the record that changed.
+/// * `before` - The record before the change, or `None` if it was created.
+/// * `after` - The record after the change, or `None` if it was removed.
+#[derive(Debug)]
+pub struct AuditEntry {
+    pub action: AuditAction,
+    pub entity: AuditEntity,
+    pub entity_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl AuditEntry {
+    pub fn new(action: AuditAction, entity: AuditEntity, entity_id: impl ToString) -> Self {
+        Self {
+            action,
+            entity,
+            entity_id: entity_id.to_string(),
+            before: None,
+            after: None,
+        }
+    }
+
+    /// Sets the snapshot of the record before the change.
+    pub fn with_before(mut self, before: &impl Serialize) -> Self {
+        self.before = serde_json::to_value(before).ok();
+        self
+    }
+
+    /// Sets the snapshot of the record after the change.
+    pub fn with_after(mut self, after: &impl Serialize) -> Self {
+        self.after = serde_json::to_value(after).ok();
+        self
+    }
+
+    /// Records that a secret field changed without recording its value.
+    pub fn with_changed_secret(mut self, field: &str) -> Self {
+        if let Some(Value::Object(after)) = &mut self.after {
+            after.insert(field.to_string(), Value::String(REDACTED.to_string()));
+        }
+        self
+    }
+}
+
+pub struct AuditService {
+    repository_container: Arc<RepositoryContainer>,
+}
+
+impl AuditService {
+    pub fn new(repository_container: Arc<RepositoryContainer>) -> Self {
+        Self {
+            repository_container,
+        }
+    }
+}
+
+impl AuditService {
+    /// Records a change in the audit log.
+    ///
+    /// The change is already committed when this is called, so a failure to record it is
+    /// logged instead of failing the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Who made the change and where the request came from.
+    /// * `entry` - The change.
+    pub async fn record(&self, context: &AuditContext, entry: AuditEntry) {
+        let (before, after) = diff(entry.before, entry.after);
+
+        let event = CreateAuditEventDTO {
+            actor_id: context.actor_id,
+            action: entry.action,
+            entity: entry.entity,
+            entity_id: entry.entity_id,
+            before,
+            after,
+            ip_address: context.ip_address.clone(),
+            request_id: context.request_id.clone(),
+        };
+
+        let (action, entity) = (event.action, event.entity);
+        let entity_id = event.entity_id.clone();
+        if let Err(e) = self
+            .repository_container
+            .audit_repo
+            .create_event(event)
+            .await
+        {
+            tracing::error!(
+                action = action.as_str(),
+                entity = entity.as_str(),
+                entity_id,
+                "failed to record audit event: {}",
+                e
+            );
+        }
+    }
+
+    /// Retrieves a page of audit events.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The pagination, sort and filter parameters.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the page of events, newest first, or
+    /// 400 (Bad Request) if the cursor or the sort field is invalid.
+    pub async fn get_audit_events(&self, query: AuditQuery) -> Response {
+        match self
+            .repository_container
+            .audit_repo
+            .get_events(&query)
+            .await
+        {
+            Ok(events) => (StatusCode::OK, Json(events)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+/// Reduces two object snapshots to the fields that differ between them.
+///
+/// Snapshots that are not both objects are kept as they are.
+fn diff(before: Option<Value>, after: Option<Value>) -> (Option<Value>, Option<Value>) {
+    match (before, after) {
+        (Some(Value::Object(mut before)), Some(Value::Object(mut after))) => {
+            let unchanged: Vec<String> = before
+                .iter()
+                .filter(|(field, value)| after.get(*field) == Some(*value))
+                .map(|(field, _)| field.clone())
+                .collect();
+            for field in &unchanged {
+                before.remove(field);
+                after.remove(field);
+            }
+
+            (Some(Value::Object(before)), Some(Value::Object(after)))
+        }
+        (before, after) => (before, after),
+    }
+}
@@ -0,0 +1,91 @@
+use crate::config::AppConfig;
+use crate::db::DbService;
+use crate::errors::AppError;
+use crate::models::health::{BuildInfoDTO, DatabaseHealthDTO, MigrationHealthDTO, ReadinessDTO};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long the readiness probe waits for the database.
+const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct HealthService {
+    db_service: Arc<DbService>,
+    app_config: Arc<AppConfig>,
+}
+
+impl HealthService {
+    pub fn new(db_service: Arc<DbService>, app_config: Arc<AppConfig>) -> Self {
+        Self {
+            db_service,
+            app_config,
+        }
+    }
+}
+
+impl HealthService {
+    /// Reports that the process is running.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK). The database is not checked, so orchestrators
+    /// do not restart the service while the database is down.
+    pub async fn liveness(&self) -> Response {
+        (StatusCode::OK, Json(json!({ "status": "alive" }))).into_response()
+    }
+
+    /// Reports whether the service can handle requests.
+    ///
+    /// The service is ready when the database answers within the readiness timeout and every
+    /// migration embedded in this build is applied.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the state of the pool, the schema version and
+    /// the build, or 503 (Service Unavailable) if the service is degraded.
+    pub async fn readiness(&self) -> Response {
+        let (latency, applied_version) = match self.db_service.probe(READINESS_TIMEOUT).await {
+            Ok(probe) => probe,
+            Err(reason) => {
+                tracing::warn!("readiness check failed: {}", reason);
+                return AppError::service_unavailable(
+                    "database_unavailable",
+                    "The database is unreachable",
+                )
+                .into_response();
+            }
+        };
+
+        let expected_version = self.db_service.latest_known_migration();
+
+        if applied_version < expected_version {
+            return AppError::service_unavailable(
+                "migrations_pending",
+                "The database schema is behind this build",
+            )
+            .into_response();
+        }
+
+        let (pool_size, idle_connections) = self.db_service.pool_usage();
+
+        let readiness = ReadinessDTO {
+            status: "ready",
+            database: DatabaseHealthDTO {
+                latency_ms: latency.as_millis(),
+                pool_size,
+                idle_connections,
+                max_connections: self.app_config.get_max_connections(),
+            },
+            migrations: MigrationHealthDTO {
+                applied_version,
+                expected_version,
+            },
+            build: BuildInfoDTO::current(),
+        };
+
+        (StatusCode::OK, Json(readiness)).into_response()
+    }
+}
@@ -0,0 +1,55 @@
+use crate::config::AppConfig;
+use crate::db::DbService;
+use crate::metrics::Metrics;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+
+/// The media type of the Prometheus text exposition format.
+const PROMETHEUS_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub struct MetricsService {
+    db_service: Arc<DbService>,
+    app_config: Arc<AppConfig>,
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsService {
+    pub fn new(
+        db_service: Arc<DbService>,
+        app_config: Arc<AppConfig>,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            db_service,
+            app_config,
+            metrics,
+        }
+    }
+}
+
+impl MetricsService {
+    /// Renders the metrics for a Prometheus scrape.
+    ///
+    /// The database pool gauges are refreshed first, so every scrape sees the current pool.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the metrics in the Prometheus text format.
+    pub async fn render(&self) -> Response {
+        let (pool_size, idle_connections) = self.db_service.pool_usage();
+        self.metrics.set_pool_usage(
+            pool_size,
+            idle_connections,
+            self.app_config.get_max_connections(),
+        );
+
+        (
+            StatusCode::OK,
+            [(CONTENT_TYPE, PROMETHEUS_TEXT)],
+            self.metrics.encode(),
+        )
+            .into_response()
+    }
+}
@@ -1,19 +1,63 @@
+use crate::config::AppConfig;
+use crate::db::DbService;
+use crate::metrics::Metrics;
 use crate::repositories::RepositoryContainer;
+use crate::services::audit_service::AuditService;
+use crate::services::health_service::HealthService;
+use crate::services::metrics_service::MetricsService;
+use crate::services::permission_service::PermissionService;
+use crate::services::role_service::RoleService;
+use crate::services::store_service::StoreService;
 use crate::services::user_access_management_service::UserAccessManagementService;
+use crate::services::user_hierarchy_service::UserHierarchyService;
 use std::sync::Arc;
 
+pub mod audit_service;
+mod health_service;
+mod metrics_service;
+mod permission_service;
+mod role_service;
+mod store_service;
 mod user_access_management_service;
+mod user_hierarchy_service;
 
 pub struct ServiceContainer {
+    pub audit_service: Arc<AuditService>,
+    pub health_service: HealthService,
+    pub metrics_service: MetricsService,
     pub user_access_management_service: UserAccessManagementService,
+    pub permission_service: PermissionService,
+    pub role_service: RoleService,
+    pub store_service: StoreService,
+    pub user_hierarchy_service: UserHierarchyService,
 }
 
 impl ServiceContainer {
-    pub fn new(repository_container: Arc<RepositoryContainer>) -> Self {
+    pub fn new(
+        db_service: Arc<DbService>,
+        repository_container: Arc<RepositoryContainer>,
+        app_config: Arc<AppConfig>,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        let audit_service = Arc::new(AuditService::new(repository_container.clone()));
+
         Self {
+            health_service: HealthService::new(db_service.clone(), app_config.clone()),
+            metrics_service: MetricsService::new(db_service, app_config.clone(), metrics.clone()),
             user_access_management_service: UserAccessManagementService::new(
                 repository_container.clone(),
+                app_config.clone(),
+                metrics,
+                audit_service.clone(),
+            ),
+            permission_service: PermissionService::new(
+                repository_container.clone(),
+                audit_service.clone(),
             ),
+            role_service: RoleService::new(repository_container.clone(), audit_service.clone()),
+            store_service: StoreService::new(repository_container.clone(), audit_service.clone()),
+            user_hierarchy_service: UserHierarchyService::new(repository_container.clone()),
+            audit_service,
         }
     }
 }
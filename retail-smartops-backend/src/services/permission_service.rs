@@ -0,0 +1,702 @@
+use crate::auth::extractor::AuditContext;
+use crate::auth::permission::{exceeds_permissions, Action};
+use crate::errors::{AppError, ErrorDetail};
+use crate::models::audit::{AuditAction, AuditEntity};
+use crate::models::permission::{CreatePermissionDTO, PermissionResponseDTO, UpdatePermissionDTO};
+use crate::repositories::RepositoryContainer;
+use crate::services::audit_service::{AuditEntry, AuditService};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The error returned when a caller tries to hand out a permission allowing more than they are allowed.
+fn permission_exceeds_caller_permissions() -> AppError {
+    AppError::Forbidden(ErrorDetail::new(
+        "permission_exceeds_caller_permissions",
+        "The permission allows actions you do not hold",
+    ))
+}
+
+pub struct PermissionService {
+    repository_container: Arc<RepositoryContainer>,
+    audit_service: Arc<AuditService>,
+}
+
+impl PermissionService {
+    pub fn new(
+        repository_container: Arc<RepositoryContainer>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            repository_container,
+            audit_service,
+        }
+    }
+
+    /// Captures the permissions held by a role, for the audit log.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Value, AppError>` - An object listing the permissions of the role or an `AppError`.
+    async fn role_permissions_snapshot(&self, role_id: i32) -> Result<Value, AppError> {
+        let permissions = self
+            .repository_container
+            .role_permission_repo
+            .get_role_permissions(role_id)
+            .await?;
+
+        Ok(json!({ "permissions": permissions }))
+    }
+
+    /// Records a change to the permissions of a role, comparing them with the permissions held before.
+    ///
+    /// # Arguments
+    ///
+    /// * `audit` - Who made the change and where the request came from.
+    /// * `action` - The kind of change.
+    /// * `role_id` - The ID of the role.
+    /// * `before` - The snapshot taken by `role_permissions_snapshot` before the change.
+    async fn record_role_permissions_change(
+        &self,
+        audit: &AuditContext,
+        action: AuditAction,
+        role_id: i32,
+        before: Value,
+    ) {
+        let mut entry =
+            AuditEntry::new(action, AuditEntity::RolePermission, role_id).with_before(&before);
+        match self.role_permissions_snapshot(role_id).await {
+            Ok(after) => entry = entry.with_after(&after),
+            Err(e) => {
+                tracing::error!(
+                    role_id,
+                    "failed to read permissions for the audit log: {}",
+                    e
+                )
+            }
+        }
+
+        self.audit_service.record(audit, entry).await;
+    }
+
+    /// Checks that a caller globally holds every action a permission allows.
+    ///
+    /// Without this check, anyone allowed to update roles could attach any permission to their
+    /// own role, and anyone allowed to update permissions could widen one their role holds.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller_id` - The UUID of the user handing out the permission.
+    /// * `permission` - The permission being handed out.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the caller may hand out the permission, or
+    ///   `AppError::Forbidden`.
+    async fn ensure_caller_holds(
+        &self,
+        caller_id: Uuid,
+        permission: &PermissionResponseDTO,
+    ) -> Result<(), AppError> {
+        let caller_permissions = self
+            .repository_container
+            .role_permission_repo
+            .get_user_permissions(caller_id, &permission.entity_name, None)
+            .await?;
+
+        if exceeds_permissions(permission, &caller_permissions) {
+            return Err(permission_exceeds_caller_permissions());
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a user may perform an action on an entity.
+    ///
+    /// The user's effective permissions are the union of the permissions of every global role
+    /// they hold and, when a store is given, every role they hold in that store while they are
+    /// a member of it. Access is granted when any of them allows the action.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The UUID of the user.
+    /// * `entity_name` - The name of the entity being accessed.
+    /// * `action` - The action being performed.
+    /// * `store_id` - The ID of the store being accessed, or `None` outside of a store.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if access is granted, `AppError::Forbidden` otherwise.
+    pub async fn authorize(
+        &self,
+        user_id: Uuid,
+        entity_name: &str,
+        action: Action,
+        store_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let permissions = self
+            .repository_container
+            .role_permission_repo
+            .get_user_permissions(user_id, entity_name, store_id)
+            .await?;
+
+        if !permissions
+            .iter()
+            .any(|permission| action.is_granted_by(permission))
+        {
+            return Err(AppError::forbidden());
+        }
+
+        Ok(())
+    }
+}
+
+impl PermissionService {
+    /// Creates a new permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The permission to create.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 201 (Created) and the created permission, or an error response.
+    pub async fn create_permission(
+        &self,
+        payload: CreatePermissionDTO,
+        audit: &AuditContext,
+    ) -> Response {
+        match self
+            .repository_container
+            .permission_repo
+            .create_permission(payload)
+            .await
+        {
+            Ok(permission) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(
+                            AuditAction::Create,
+                            AuditEntity::Permission,
+                            permission.id,
+                        )
+                        .with_after(&permission),
+                    )
+                    .await;
+                (StatusCode::CREATED, Json(permission)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves a permission by its ID.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the permission, or 404 (Not Found).
+    pub async fn get_permission(&self, id: i32) -> Response {
+        match self
+            .repository_container
+            .permission_repo
+            .get_permission_by_id(id)
+            .await
+        {
+            Ok(permission) => (StatusCode::OK, Json(permission)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Updates an existing permission.
+    ///
+    /// Every flag the update turns on must be held globally by the caller. Moving the
+    /// permission to another entity turns on all of its flags for that entity.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The permission ID.
+    /// * `payload` - The fields to change.
+    /// * `caller_id` - The UUID of the user updating the permission.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the updated permission, 403 (Forbidden) if the
+    /// update turns on a flag the caller does not hold, or 404 (Not Found).
+    pub async fn update_permission(
+        &self,
+        id: i32,
+        payload: UpdatePermissionDTO,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        let current = match self
+            .repository_container
+            .permission_repo
+            .get_permission_by_id(id)
+            .await
+        {
+            Ok(current) => current,
+            Err(e) => return e.into_response(),
+        };
+
+        let entity_name = payload
+            .entity_name
+            .clone()
+            .unwrap_or_else(|| current.entity_name.clone());
+        let moved = entity_name != current.entity_name;
+        let turned_on = |new: Option<bool>, old: bool| new.unwrap_or(old) && (moved || !old);
+        let granted = PermissionResponseDTO {
+            id,
+            can_read: turned_on(payload.can_read, current.can_read),
+            can_write: turned_on(payload.can_write, current.can_write),
+            can_delete: turned_on(payload.can_delete, current.can_delete),
+            can_update: turned_on(payload.can_update, current.can_update),
+            entity_name,
+        };
+        if let Err(e) = self.ensure_caller_holds(caller_id, &granted).await {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .permission_repo
+            .update_permission(id, payload)
+            .await
+        {
+            Ok(permission) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Update, AuditEntity::Permission, id)
+                            .with_before(&current)
+                            .with_after(&permission),
+                    )
+                    .await;
+                (StatusCode::OK, Json(permission)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Deletes a permission. Roles holding it lose it as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The permission ID.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found).
+    pub async fn delete_permission(&self, id: i32, audit: &AuditContext) -> Response {
+        let permission_repo = &self.repository_container.permission_repo;
+
+        let before = match permission_repo.get_permission_by_id(id).await {
+            Ok(permission) => permission,
+            Err(e) => return e.into_response(),
+        };
+
+        match permission_repo.delete_permission(id).await {
+            Ok(()) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Delete, AuditEntity::Permission, id)
+                            .with_before(&before),
+                    )
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves all permissions.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the list of permissions.
+    pub async fn get_permissions(&self) -> Response {
+        match self
+            .repository_container
+            .permission_repo
+            .get_permissions()
+            .await
+        {
+            Ok(permissions) => (StatusCode::OK, Json(permissions)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Assigns a permission to a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    /// * `permission_id` - The ID of the permission.
+    /// * `caller_id` - The UUID of the user granting the permission.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 201 (Created), 403 (Forbidden) if the permission allows
+    /// actions the caller does not hold globally, 404 (Not Found) if the role or the permission
+    /// does not exist, or 409 (Conflict) if the role already holds the permission.
+    pub async fn grant_role_permission(
+        &self,
+        role_id: i32,
+        permission_id: i32,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Err(e) = self
+            .repository_container
+            .role_repo
+            .get_role_by_id(role_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        let permission = match self
+            .repository_container
+            .permission_repo
+            .get_permission_by_id(permission_id)
+            .await
+        {
+            Ok(permission) => permission,
+            Err(e) => return e.into_response(),
+        };
+
+        if let Err(e) = self.ensure_caller_holds(caller_id, &permission).await {
+            return e.into_response();
+        }
+
+        let before = match self.role_permissions_snapshot(role_id).await {
+            Ok(before) => before,
+            Err(e) => return e.into_response(),
+        };
+
+        match self
+            .repository_container
+            .role_permission_repo
+            .add_role_permission(role_id, permission_id)
+            .await
+        {
+            Ok(role_permission) => {
+                self.record_role_permissions_change(audit, AuditAction::Create, role_id, before)
+                    .await;
+                (StatusCode::CREATED, Json(role_permission)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Removes a permission from a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `role_id` - The ID of the role.
+    /// * `permission_id` - The ID of the permission.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found).
+    pub async fn revoke_role_permission(
+        &self,
+        role_id: i32,
+        permission_id: i32,
+        audit: &AuditContext,
+    ) -> Response {
+        let before = match self.role_permissions_snapshot(role_id).await {
+            Ok(before) => before,
+            Err(e) => return e.into_response(),
+        };
+
+        match self
+            .repository_container
+            .role_permission_repo
+            .delete_role_permission(role_id, permission_id)
+            .await
+        {
+            Ok(()) => {
+                self.record_role_permissions_change(audit, AuditAction::Delete, role_id, before)
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the permissions assigned to a role.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the permissions, or 404 (Not Found) if the role does not exist.
+    pub async fn get_role_permissions(&self, role_id: i32) -> Response {
+        if let Err(e) = self
+            .repository_container
+            .role_repo
+            .get_role_by_id(role_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .role_permission_repo
+            .get_role_permissions(role_id)
+            .await
+        {
+            Ok(permissions) => (StatusCode::OK, Json(permissions)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        add_store_user, create_role, create_store, create_user, grant_role, json_body,
+    };
+    use serde_json::Value;
+    use sqlx::PgPool;
+
+    /// An audit event as `(action, actor_id, request_id, before, after)`.
+    type AuditRow = (
+        String,
+        Option<Uuid>,
+        Option<String>,
+        Option<Value>,
+        Option<Value>,
+    );
+
+    fn service(pool: &PgPool) -> PermissionService {
+        let repository_container = Arc::new(RepositoryContainer::new(pool.clone()));
+        let audit_service = Arc::new(AuditService::new(repository_container.clone()));
+
+        PermissionService::new(repository_container, audit_service)
+    }
+
+    async fn create_permission(pool: &PgPool, entity_name: &str, can_delete: bool) -> i32 {
+        sqlx::query_scalar(
+            "INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update) \
+             VALUES ($1, TRUE, FALSE, $2, FALSE) RETURNING id",
+        )
+        .bind(entity_name)
+        .bind(can_delete)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    fn update(entity_name: Option<&str>, can_delete: Option<bool>) -> UpdatePermissionDTO {
+        UpdatePermissionDTO {
+            entity_name: entity_name.map(str::to_string),
+            can_read: None,
+            can_write: None,
+            can_delete,
+            can_update: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn granting_a_permission_beyond_the_caller_is_forbidden(pool: PgPool) {
+        let service = service(&pool);
+        let caller = create_user(&pool, "editor").await;
+        let editor = create_role(&pool, "editor", &[("roles", true, false, false, true)]).await;
+        grant_role(&pool, caller, editor, None).await;
+        let purge = create_permission(&pool, "user_purge", true).await;
+        let read_roles = create_permission(&pool, "roles", false).await;
+
+        let escalation = service
+            .grant_role_permission(editor, purge, caller, &AuditContext::default())
+            .await;
+        assert_eq!(escalation.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            json_body(escalation).await["code"],
+            "permission_exceeds_caller_permissions"
+        );
+
+        let within = service
+            .grant_role_permission(editor, read_roles, caller, &AuditContext::default())
+            .await;
+        assert_eq!(within.status(), StatusCode::CREATED);
+    }
+
+    #[sqlx::test]
+    async fn store_roles_do_not_count_towards_granting(pool: PgPool) {
+        let service = service(&pool);
+        let caller = create_user(&pool, "editor").await;
+        let editor = create_role(&pool, "editor", &[("roles", true, false, false, true)]).await;
+        let manager = create_role(&pool, "manager", &[("sales", true, true, true, true)]).await;
+        grant_role(&pool, caller, editor, None).await;
+        let store_id = create_store(&pool, caller).await;
+        add_store_user(&pool, store_id, caller).await;
+        grant_role(&pool, caller, manager, Some(store_id)).await;
+        let sales = create_permission(&pool, "sales", true).await;
+
+        let response = service
+            .grant_role_permission(editor, sales, caller, &AuditContext::default())
+            .await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[sqlx::test]
+    async fn updating_a_permission_cannot_turn_on_flags_beyond_the_caller(pool: PgPool) {
+        let service = service(&pool);
+        let caller = create_user(&pool, "editor").await;
+        let editor = create_role(
+            &pool,
+            "editor",
+            &[("permissions", true, false, false, true)],
+        )
+        .await;
+        grant_role(&pool, caller, editor, None).await;
+        let own: i32 =
+            sqlx::query_scalar("SELECT permission_id FROM role_permissions WHERE role_id = $1")
+                .bind(editor)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        let widened = service
+            .update_permission(
+                own,
+                update(None, Some(true)),
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(widened.status(), StatusCode::FORBIDDEN);
+
+        let moved = service
+            .update_permission(
+                own,
+                update(Some("users"), None),
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(moved.status(), StatusCode::FORBIDDEN);
+
+        let narrowed = service
+            .update_permission(
+                own,
+                update(None, Some(false)),
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(narrowed.status(), StatusCode::OK);
+        let can_delete: bool =
+            sqlx::query_scalar("SELECT can_delete FROM permissions WHERE id = $1")
+                .bind(own)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(!can_delete);
+    }
+
+    #[sqlx::test]
+    async fn store_roles_only_apply_during_the_membership(pool: PgPool) {
+        let service = service(&pool);
+        let repository_container = RepositoryContainer::new(pool.clone());
+        let owner = create_user(&pool, "owner").await;
+        let cashier = create_user(&pool, "cashier").await;
+        let role = create_role(&pool, "cashier", &[("sales", true, true, false, false)]).await;
+        let store_id = create_store(&pool, owner).await;
+        let other_store_id = create_store(&pool, owner).await;
+        add_store_user(&pool, store_id, cashier).await;
+        grant_role(&pool, cashier, role, Some(store_id)).await;
+
+        assert!(service
+            .authorize(cashier, "sales", Action::Write, Some(store_id))
+            .await
+            .is_ok());
+        assert!(service
+            .authorize(cashier, "sales", Action::Write, Some(other_store_id))
+            .await
+            .is_err());
+        assert!(service
+            .authorize(cashier, "sales", Action::Write, None)
+            .await
+            .is_err());
+
+        repository_container
+            .store_user_repo
+            .remove_store_user(store_id, cashier)
+            .await
+            .unwrap();
+
+        for action in Action::ALL {
+            assert!(matches!(
+                service
+                    .authorize(cashier, "sales", action, Some(store_id))
+                    .await,
+                Err(AppError::Forbidden(_))
+            ));
+        }
+    }
+
+    #[sqlx::test]
+    async fn permission_changes_are_audited(pool: PgPool) {
+        let service = service(&pool);
+        let caller = create_user(&pool, "editor").await;
+        let editor = create_role(
+            &pool,
+            "editor",
+            &[
+                ("permissions", true, true, true, true),
+                ("sales", true, true, true, true),
+            ],
+        )
+        .await;
+        grant_role(&pool, caller, editor, None).await;
+        let sales = create_permission(&pool, "sales", false).await;
+        let audit = AuditContext {
+            actor_id: Some(caller),
+            ip_address: None,
+            request_id: Some("req-42".to_string()),
+        };
+
+        let updated = service
+            .update_permission(sales, update(None, Some(true)), caller, &audit)
+            .await;
+        assert_eq!(updated.status(), StatusCode::OK);
+        let deleted = service.delete_permission(sales, &audit).await;
+        assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+
+        let events: Vec<AuditRow> = sqlx::query_as(
+            "SELECT action, actor_id, request_id, before, after FROM audit_events \
+                 WHERE entity = 'permission' AND entity_id = $1 ORDER BY id",
+        )
+        .bind(sales.to_string())
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(events.len(), 2);
+        let (action, actor_id, request_id, before, after) = &events[0];
+        assert_eq!(action, "update");
+        assert_eq!(*actor_id, Some(caller));
+        assert_eq!(request_id.as_deref(), Some("req-42"));
+        assert_eq!(before.as_ref().unwrap()["can_delete"], false);
+        assert_eq!(after.as_ref().unwrap()["can_delete"], true);
+        let (action, _, _, before, after) = &events[1];
+        assert_eq!(action, "delete");
+        assert_eq!(before.as_ref().unwrap()["can_delete"], true);
+        assert!(after.is_none());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::auth::extractor::AuditContext;
+use crate::models::audit::{AuditAction, AuditEntity};
+use crate::models::list::ListQuery;
+use crate::models::role::{CreateRoleDTO, UpdateRoleDTO};
+use crate::repositories::RepositoryContainer;
+use crate::services::audit_service::{AuditEntry, AuditService};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+
+pub struct RoleService {
+    repository_container: Arc<RepositoryContainer>,
+    audit_service: Arc<AuditService>,
+}
+
+impl RoleService {
+    pub fn new(
+        repository_container: Arc<RepositoryContainer>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            repository_container,
+            audit_service,
+        }
+    }
+}
+
+impl RoleService {
+    /// Creates a new role.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 201 (Created) and the created role, or 409 (Conflict)
+    /// if a role with the same name already exists.
+    pub async fn create_role(&self, payload: CreateRoleDTO, audit: &AuditContext) -> Response {
+        match self
+            .repository_container
+            .role_repo
+            .create_role(payload)
+            .await
+        {
+            Ok(role) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Create, AuditEntity::Role, role.id)
+                            .with_after(&role),
+                    )
+                    .await;
+                (StatusCode::CREATED, Json(role)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves a role by its ID.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the role, or 404 (Not Found).
+    pub async fn get_role(&self, id: i32) -> Response {
+        match self.repository_container.role_repo.get_role_by_id(id).await {
+            Ok(role) => (StatusCode::OK, Json(role)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Updates an existing role.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the updated role, 404 (Not Found), or
+    /// 409 (Conflict) if another role already has the new name.
+    pub async fn update_role(
+        &self,
+        id: i32,
+        payload: UpdateRoleDTO,
+        audit: &AuditContext,
+    ) -> Response {
+        let role_repo = &self.repository_container.role_repo;
+
+        let before = match role_repo.get_role_by_id(id).await {
+            Ok(role) => role,
+            Err(e) => return e.into_response(),
+        };
+
+        match role_repo.update_role(id, payload).await {
+            Ok(role) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Update, AuditEntity::Role, id)
+                            .with_before(&before)
+                            .with_after(&role),
+                    )
+                    .await;
+                (StatusCode::OK, Json(role)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Deletes a role. Users holding it lose it as well.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found).
+    pub async fn delete_role(&self, id: i32, audit: &AuditContext) -> Response {
+        let role_repo = &self.repository_container.role_repo;
+
+        let before = match role_repo.get_role_by_id(id).await {
+            Ok(role) => role,
+            Err(e) => return e.into_response(),
+        };
+
+        match role_repo.delete_role(id).await {
+            Ok(()) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Delete, AuditEntity::Role, id)
+                            .with_before(&before),
+                    )
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves a page of roles.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the page of roles, or 400 (Bad Request) if the
+    /// cursor or the sort field is invalid.
+    pub async fn get_roles(&self, query: ListQuery) -> Response {
+        match self.repository_container.role_repo.get_roles(&query).await {
+            Ok(roles) => (StatusCode::OK, Json(roles)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the users holding a role.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the members, or 404 (Not Found) if the role does not exist.
+    pub async fn get_role_members(&self, id: i32) -> Response {
+        if let Err(e) = self.repository_container.role_repo.get_role_by_id(id).await {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .user_role_repo
+            .list_users_for_role(id)
+            .await
+        {
+            Ok(members) => (StatusCode::OK, Json(members)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use crate::auth::extractor::AuditContext;
+use crate::auth::permission::Action;
+use crate::errors::{AppError, ErrorDetail};
+use crate::models::audit::{AuditAction, AuditEntity};
+use crate::models::list::ListQuery;
+use crate::models::store::{CreateStoreDTO, StoreResponseDTO, UpdateStoreDTO};
+use crate::models::store_users::AddStoreUserDTO;
+use crate::repositories::RepositoryContainer;
+use crate::services::audit_service::{AuditEntry, AuditService};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The error returned when a caller holding `stores` update only in the store tries to hand it over.
+fn owner_change_requires_global_permission() -> AppError {
+    AppError::Forbidden(ErrorDetail::new(
+        "owner_change_requires_global_permission",
+        "Changing the owner of a store requires the stores update permission outside of the store",
+    ))
+}
+
+pub struct StoreService {
+    repository_container: Arc<RepositoryContainer>,
+    audit_service: Arc<AuditService>,
+}
+
+impl StoreService {
+    pub fn new(
+        repository_container: Arc<RepositoryContainer>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            repository_container,
+            audit_service,
+        }
+    }
+
+    /// Checks that a user referenced by a payload exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the payload field holding the reference.
+    /// * `user_id` - The UUID of the referenced user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the user exists, `AppError::UnprocessableEntity` otherwise.
+    async fn validate_user_reference(&self, field: &str, user_id: Uuid) -> Result<(), AppError> {
+        match self
+            .repository_container
+            .user_repo
+            .get_user_by_id(user_id)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(AppError::NotFound(_)) => Err(AppError::UnprocessableEntity(
+                ErrorDetail::new("user_not_found", "The referenced user does not exist")
+                    .with_field(field, "does not reference an existing user"),
+            )),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Checks that a caller may update stores through a global role.
+    ///
+    /// The store update route also admits roles held in the store, which is enough to edit it
+    /// but not to give it away.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller_id` - The UUID of the caller.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if a global role of the caller allows updating stores,
+    ///   `AppError::Forbidden` otherwise.
+    async fn ensure_global_store_update(&self, caller_id: Uuid) -> Result<(), AppError> {
+        let permissions = self
+            .repository_container
+            .role_permission_repo
+            .get_user_permissions(caller_id, "stores", None)
+            .await?;
+
+        if !permissions
+            .iter()
+            .any(|permission| Action::Update.is_granted_by(permission))
+        {
+            return Err(owner_change_requires_global_permission());
+        }
+
+        Ok(())
+    }
+}
+
+impl StoreService {
+    /// Creates a new store.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 201 (Created) and the created store, or 422 (Unprocessable Entity)
+    /// if the payload is invalid or the owner does not exist.
+    pub async fn create_store(&self, payload: CreateStoreDTO, audit: &AuditContext) -> Response {
+        if let Err(e) = self
+            .validate_user_reference("owner_id", payload.owner_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .store_repo
+            .create_store(payload)
+            .await
+        {
+            Ok(store) => {
+                let store = StoreResponseDTO::from(store);
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Create, AuditEntity::Store, store.id)
+                            .with_after(&store),
+                    )
+                    .await;
+                (StatusCode::CREATED, Json(store)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves a store by its ID.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the store, or 404 (Not Found).
+    pub async fn get_store(&self, id: i32) -> Response {
+        match self
+            .repository_container
+            .store_repo
+            .get_store_by_id(id)
+            .await
+        {
+            Ok(store) => (StatusCode::OK, Json(StoreResponseDTO::from(store))).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Updates an existing store.
+    ///
+    /// Changing the owner takes the `stores` update permission through a global role; roles
+    /// held in the store only allow editing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The store ID.
+    /// * `payload` - The fields to change.
+    /// * `caller_id` - The UUID of the user updating the store.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the updated store, 403 (Forbidden) if the owner
+    /// would change and the caller may only update this store, 404 (Not Found), or
+    /// 422 (Unprocessable Entity) if the payload is invalid or the new owner does not exist.
+    pub async fn update_store(
+        &self,
+        id: i32,
+        payload: UpdateStoreDTO,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Some(owner_id) = payload.owner_id {
+            if let Err(e) = self.validate_user_reference("owner_id", owner_id).await {
+                return e.into_response();
+            }
+        }
+
+        let store_repo = &self.repository_container.store_repo;
+
+        let before = match store_repo.get_store_by_id(id).await {
+            Ok(store) => StoreResponseDTO::from(store),
+            Err(e) => return e.into_response(),
+        };
+
+        if payload
+            .owner_id
+            .is_some_and(|owner_id| owner_id != before.owner_id)
+        {
+            if let Err(e) = self.ensure_global_store_update(caller_id).await {
+                return e.into_response();
+            }
+        }
+
+        match store_repo.update_store(id, payload).await {
+            Ok(store) => {
+                let store = StoreResponseDTO::from(store);
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Update, AuditEntity::Store, id)
+                            .with_before(&before)
+                            .with_after(&store),
+                    )
+                    .await;
+                (StatusCode::OK, Json(store)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Deletes a store.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found).
+    pub async fn delete_store(&self, id: i32, audit: &AuditContext) -> Response {
+        let store_repo = &self.repository_container.store_repo;
+
+        let before = match store_repo.get_store_by_id(id).await {
+            Ok(store) => StoreResponseDTO::from(store),
+            Err(e) => return e.into_response(),
+        };
+
+        match store_repo.delete_store(id).await {
+            Ok(()) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Delete, AuditEntity::Store, id)
+                            .with_before(&before),
+                    )
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves a page of stores.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the page of stores, or 400 (Bad Request) if the
+    /// cursor or the sort field is invalid.
+    pub async fn get_stores(&self, query: ListQuery) -> Response {
+        match self
+            .repository_container
+            .store_repo
+            .get_stores(&query)
+            .await
+        {
+            Ok(stores) => (StatusCode::OK, Json(stores)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Adds a user to the staff of a store.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 201 (Created) and the membership, 404 (Not Found) if the store
+    /// does not exist, 409 (Conflict) if the user is already a member, or 422 (Unprocessable Entity)
+    /// if the user does not exist or the membership period is invalid.
+    pub async fn add_store_user(
+        &self,
+        store_id: i32,
+        payload: AddStoreUserDTO,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Err(e) = self
+            .repository_container
+            .store_repo
+            .get_store_by_id(store_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        if let Err(e) = self
+            .validate_user_reference("user_id", payload.user_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .store_user_repo
+            .add_store_user(store_id, payload)
+            .await
+        {
+            Ok(store_user) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(
+                            AuditAction::Create,
+                            AuditEntity::StoreUser,
+                            format!("{}:{}", store_id, store_user.user_id),
+                        )
+                        .with_after(&store_user),
+                    )
+                    .await;
+                (StatusCode::CREATED, Json(store_user)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Removes a user from the staff of a store.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found) if the user is not a member.
+    pub async fn remove_store_user(
+        &self,
+        store_id: i32,
+        user_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        match self
+            .repository_container
+            .store_user_repo
+            .remove_store_user(store_id, user_id)
+            .await
+        {
+            Ok(store_user) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(
+                            AuditAction::Delete,
+                            AuditEntity::StoreUser,
+                            format!("{}:{}", store_id, user_id),
+                        )
+                        .with_before(&store_user),
+                    )
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the current staff of a store with the roles they hold there.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the staff, or 404 (Not Found) if the store does not exist.
+    pub async fn get_store_staff(&self, store_id: i32) -> Response {
+        if let Err(e) = self
+            .repository_container
+            .store_repo
+            .get_store_by_id(store_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .store_user_repo
+            .list_store_staff(store_id)
+            .await
+        {
+            Ok(staff) => (StatusCode::OK, Json(staff)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the stores a user currently works at.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the stores, or 404 (Not Found) if the user does not exist.
+    pub async fn get_user_stores(&self, user_id: Uuid) -> Response {
+        if let Err(e) = self
+            .repository_container
+            .user_repo
+            .get_user_by_id(user_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .store_user_repo
+            .list_user_stores(user_id)
+            .await
+        {
+            Ok(stores) => (StatusCode::OK, Json(stores)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
@@ -1,55 +1,1776 @@
-use crate::models::user::{CreateUserDTO, UpdateUserDTO};
+use crate::auth::extractor::AuditContext;
+use crate::auth::password::{hash_password, verify_password, DUMMY_PASSWORD_HASH};
+use crate::auth::permission::exceeds_permissions;
+use crate::auth::token::{generate_access_token, generate_refresh_token, hash_refresh_token};
+use crate::config::AppConfig;
+use crate::errors::{AppError, ErrorDetail};
+use crate::metrics::{DomainEvent, Metrics};
+use crate::models::audit::{AuditAction, AuditEntity};
+use crate::models::auth::{
+    LoginAttemptDTO, LoginThrottlePolicy, LoginThrottleScope, TokenResponseDTO, UnlockLoginDTO,
+};
+use crate::models::employee::{EmployeeLifecycleEventDTO, EmployeeResponseDTO};
+use crate::models::list::ListQuery;
+use crate::models::user::{CreateUserDTO, PurgeUserQuery, UpdateUserDTO};
+use crate::models::user_role::{AssignUserRoleDTO, ReplaceUserRoleDTO, SetUserRolesDTO};
 use crate::repositories::RepositoryContainer;
-use axum::response::Response;
+use crate::services::audit_service::{AuditEntry, AuditService};
+use axum::http::header::RETRY_AFTER;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
+use std::net::IpAddr;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// The error returned for a failed login, whatever the reason it failed.
+fn invalid_credentials() -> AppError {
+    AppError::Unauthorized(ErrorDetail::new(
+        "invalid_credentials",
+        "The username or password is incorrect",
+    ))
+}
+
+/// The longest username a user can have. Failed logins for longer usernames are only counted
+/// against the IP address, since no account can be locked out by them.
+const MAX_USERNAME_LENGTH: usize = 50;
+
+/// The response returned while a login is locked out, telling the client when to retry.
+fn login_locked(locked_until: DateTime<Utc>) -> Response {
+    let retry_after = (locked_until - Utc::now()).num_seconds().max(0) + 1;
+
+    let mut response =
+        AppError::too_many_requests("login_locked", "Too many failed logins, try again later")
+            .into_response();
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    response
+}
+
+/// The error returned when a caller tries to grant a role allowing more than they are allowed.
+fn role_exceeds_caller_permissions() -> AppError {
+    AppError::Forbidden(ErrorDetail::new(
+        "role_exceeds_caller_permissions",
+        "The role grants permissions you do not hold",
+    ))
+}
+
+/// The error returned when a caller tries to change the credentials of a user allowed more than they are.
+fn user_exceeds_caller_permissions() -> AppError {
+    AppError::Forbidden(ErrorDetail::new(
+        "user_exceeds_caller_permissions",
+        "The user holds permissions you do not hold",
+    ))
+}
+
+/// The error returned when a refresh token matches no active session.
+fn invalid_refresh_token() -> AppError {
+    AppError::Unauthorized(ErrorDetail::new(
+        "invalid_refresh_token",
+        "The refresh token is invalid, expired or already used",
+    ))
+}
+
 pub struct UserAccessManagementService {
     repository_container: Arc<RepositoryContainer>,
+    app_config: Arc<AppConfig>,
+    metrics: Arc<Metrics>,
+    audit_service: Arc<AuditService>,
 }
 
 impl UserAccessManagementService {
-    pub fn new(repository_container: Arc<RepositoryContainer>) -> Self {
+    pub fn new(
+        repository_container: Arc<RepositoryContainer>,
+        app_config: Arc<AppConfig>,
+        metrics: Arc<Metrics>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
         Self {
             repository_container,
+            app_config,
+            metrics,
+            audit_service,
+        }
+    }
+
+    /// Issues an access token and a refresh token for a user and records the session.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The unique identifier of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TokenResponseDTO, AppError>` - The issued tokens or an `AppError`.
+    async fn issue_tokens(&self, user_id: Uuid) -> Result<TokenResponseDTO, AppError> {
+        let access_token_ttl = self.app_config.get_access_token_ttl();
+        let access_token =
+            generate_access_token(user_id, self.app_config.get_jwt_secret(), access_token_ttl)?;
+
+        let refresh_token = generate_refresh_token();
+        let expires_at =
+            Utc::now() + Duration::seconds(self.app_config.get_refresh_token_ttl() as i64);
+
+        self.repository_container
+            .session_repo
+            .create_session(user_id, &hash_refresh_token(&refresh_token), expires_at)
+            .await?;
+
+        Ok(TokenResponseDTO {
+            access_token,
+            refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: access_token_ttl,
+        })
+    }
+
+    /// Verifies a username and password pair.
+    ///
+    /// Unknown users, inactive users and wrong passwords are all reported as
+    /// `AppError::Unauthorized` so callers cannot probe which usernames exist. The password is
+    /// verified before anything else is decided, against `DUMMY_PASSWORD_HASH` for unknown
+    /// users, so response times do not tell the cases apart either.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `password` - The plaintext password of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Uuid, AppError>` - The ID of the authenticated user or an `AppError`.
+    async fn authenticate(&self, username: &str, password: &str) -> Result<Uuid, AppError> {
+        let user = match self
+            .repository_container
+            .user_repo
+            .get_user_entity_by_username(username)
+            .await
+        {
+            Ok(user) => Some(user),
+            Err(AppError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let password_hash = user
+            .as_ref()
+            .map_or(DUMMY_PASSWORD_HASH, |user| user.password.as_str());
+        let password_matches = verify_password(password, password_hash)?;
+
+        match user {
+            Some(user) if password_matches && user.is_active => Ok(user.id),
+            _ => Err(invalid_credentials()),
+        }
+    }
+
+    /// Lists the keys failed logins are counted against, with the policy locking each of
+    /// them out.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The submitted username.
+    /// * `ip_address` - The IP address the login came from, if known.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<(LoginThrottleScope, String, LoginThrottlePolicy)>` with the scope, key and
+    /// policy of each key.
+    fn login_throttle_keys(
+        &self,
+        username: &str,
+        ip_address: Option<IpAddr>,
+    ) -> Vec<(LoginThrottleScope, String, LoginThrottlePolicy)> {
+        let policy = |max_attempts| LoginThrottlePolicy {
+            max_attempts,
+            attempt_window: self.app_config.get_login_attempt_window(),
+            lockout_base: self.app_config.get_login_lockout_base(),
+            lockout_max: self.app_config.get_login_lockout_max(),
+        };
+
+        let mut keys = Vec::with_capacity(2);
+        if username.chars().count() <= MAX_USERNAME_LENGTH {
+            keys.push((
+                LoginThrottleScope::Username,
+                username.to_string(),
+                policy(self.app_config.get_login_max_attempts_per_username()),
+            ));
+        }
+        if let Some(ip_address) = ip_address {
+            keys.push((
+                LoginThrottleScope::Ip,
+                ip_address.to_string(),
+                policy(self.app_config.get_login_max_attempts_per_ip()),
+            ));
+        }
+        keys
+    }
+
+    /// Counts a login attempt against every key, before the password is verified.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys returned by `login_throttle_keys`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<LoginAttemptDTO>, AppError>` - The outcome for each key, in the same
+    ///   order, or an `AppError`.
+    async fn count_login_attempt(
+        &self,
+        keys: &[(LoginThrottleScope, String, LoginThrottlePolicy)],
+    ) -> Result<Vec<LoginAttemptDTO>, AppError> {
+        let mut attempts = Vec::with_capacity(keys.len());
+        for (scope, key, policy) in keys {
+            attempts.push(
+                self.repository_container
+                    .login_throttle_repo
+                    .record_failure(*scope, key, policy)
+                    .await?,
+            );
+        }
+        Ok(attempts)
+    }
+
+    /// Takes back a login attempt that succeeded.
+    ///
+    /// The count of the username starts over. The attempt is only taken back from the count
+    /// of the IP address, which may be shared by other users.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys returned by `login_throttle_keys`.
+    /// * `attempts` - The outcomes returned by `count_login_attempt`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the attempt was taken back, or an `AppError`.
+    async fn forgive_login_attempt(
+        &self,
+        keys: &[(LoginThrottleScope, String, LoginThrottlePolicy)],
+        attempts: &[LoginAttemptDTO],
+    ) -> Result<(), AppError> {
+        let login_throttle_repo = &self.repository_container.login_throttle_repo;
+
+        for ((scope, key, _), attempt) in keys.iter().zip(attempts) {
+            match scope {
+                LoginThrottleScope::Username => {
+                    login_throttle_repo.clear(*scope, key).await?;
+                }
+                LoginThrottleScope::Ip => {
+                    login_throttle_repo
+                        .forgive(*scope, key, attempt.locked_until)
+                        .await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Logs the lockouts started by a failed login attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The submitted username.
+    /// * `ip_address` - The IP address the login came from, if known.
+    /// * `keys` - The keys returned by `login_throttle_keys`.
+    /// * `attempts` - The outcomes returned by `count_login_attempt`.
+    fn log_lockouts(
+        &self,
+        username: &str,
+        ip_address: Option<IpAddr>,
+        keys: &[(LoginThrottleScope, String, LoginThrottlePolicy)],
+        attempts: &[LoginAttemptDTO],
+    ) {
+        for ((scope, _, _), attempt) in keys.iter().zip(attempts) {
+            let Some(locked_until) = attempt.locked_until.filter(|_| attempt.counted) else {
+                continue;
+            };
+
+            tracing::warn!(
+                username,
+                ip_address = ip_address.map(|ip_address| ip_address.to_string()),
+                scope = scope.as_str(),
+                failed_attempts = attempt.failed_attempts,
+                locked_until = %locked_until,
+                "login locked out"
+            );
+        }
+    }
+
+    /// Exchanges a refresh token for a new token pair.
+    ///
+    /// The presented session is revoked and a new one is created, so every refresh token
+    /// can be used only once. Sessions of deactivated or deleted users are not refreshed.
+    ///
+    /// # Arguments
+    ///
+    /// * `refresh_token` - The refresh token issued at login or at the previous refresh.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TokenResponseDTO, AppError>` - The new tokens or an `AppError`.
+    async fn rotate_refresh_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<TokenResponseDTO, AppError> {
+        let session = match self
+            .repository_container
+            .session_repo
+            .revoke_active_session(&hash_refresh_token(refresh_token))
+            .await
+        {
+            Ok(session) => session,
+            Err(AppError::NotFound(_)) => return Err(invalid_refresh_token()),
+            Err(e) => return Err(e),
+        };
+
+        // `get_user_entity_by_id` skips deleted users, so only deactivation is left to check.
+        let user = match self
+            .repository_container
+            .user_repo
+            .get_user_entity_by_id(session.user_id)
+            .await
+        {
+            Ok(user) => user,
+            Err(AppError::NotFound(_)) => return Err(invalid_refresh_token()),
+            Err(e) => return Err(e),
+        };
+        if !user.is_active {
+            return Err(invalid_refresh_token());
+        }
+
+        self.issue_tokens(user.id).await
+    }
+
+    /// Hashes the new password of a user update, if present, with argon2id.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The data transfer object containing user update details.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<UpdateUserDTO, AppError>` - The payload ready to be persisted or an `AppError`.
+    fn prepare_user_update(&self, payload: UpdateUserDTO) -> Result<UpdateUserDTO, AppError> {
+        let password = payload.password.as_deref().map(hash_password).transpose()?;
+
+        Ok(UpdateUserDTO {
+            password,
+            ..payload
+        })
+    }
+
+    /// Captures the roles held by a user, for the audit log.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Value, AppError>` - An object listing the roles of the user or an `AppError`.
+    async fn user_roles_snapshot(&self, id: Uuid) -> Result<Value, AppError> {
+        let roles = self
+            .repository_container
+            .user_role_repo
+            .list_roles_for_user(id)
+            .await?;
+
+        Ok(json!({ "roles": roles }))
+    }
+
+    /// Checks whether a role allows an action the caller does not hold in the same scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller_id` - The UUID of the caller.
+    /// * `role_id` - The ID of the role.
+    /// * `store_id` - The store the role applies in, or `None` for a global assignment.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, AppError>` - `true` if the role allows more than the caller, or an `AppError`.
+    async fn role_exceeds_caller(
+        &self,
+        caller_id: Uuid,
+        role_id: i32,
+        store_id: Option<i32>,
+    ) -> Result<bool, AppError> {
+        let role_permission_repo = &self.repository_container.role_permission_repo;
+
+        for permission in role_permission_repo.get_role_permissions(role_id).await? {
+            let caller_permissions = role_permission_repo
+                .get_user_permissions(caller_id, &permission.entity_name, store_id)
+                .await?;
+
+            if exceeds_permissions(&permission, &caller_permissions) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Checks that a caller holds every permission of a role they want to grant.
+    ///
+    /// Without this check, anyone allowed to assign roles could hand themselves or an
+    /// accomplice a role more powerful than their own, e.g. `admin`.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller_id` - The UUID of the user granting the role.
+    /// * `role_id` - The ID of the role being granted.
+    /// * `store_id` - The store the role is granted in, or `None` for a global assignment.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the caller may grant the role, or
+    ///   `AppError::Forbidden`.
+    async fn ensure_can_grant(
+        &self,
+        caller_id: Uuid,
+        role_id: i32,
+        store_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        if self
+            .role_exceeds_caller(caller_id, role_id, store_id)
+            .await?
+        {
+            return Err(role_exceeds_caller_permissions());
         }
+
+        Ok(())
+    }
+
+    /// Checks that a caller may change the username or password of a user.
+    ///
+    /// Whoever sets a user's password can log in as that user, so it takes holding every
+    /// permission of every role the user holds, by the same rule as granting those roles.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller_id` - The UUID of the user making the change.
+    /// * `id` - The UUID of the user whose credentials change.
+    /// * `payload` - The requested update.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the update leaves the credentials alone or the
+    ///   caller may change them, or `AppError::Forbidden`.
+    async fn ensure_can_change_credentials(
+        &self,
+        caller_id: Uuid,
+        id: Uuid,
+        payload: &UpdateUserDTO,
+    ) -> Result<(), AppError> {
+        if payload.username.is_none() && payload.password.is_none() {
+            return Ok(());
+        }
+
+        let roles = self
+            .repository_container
+            .user_role_repo
+            .list_roles_for_user(id)
+            .await?;
+        for role in roles {
+            if self
+                .role_exceeds_caller(caller_id, role.role_id, role.store_id)
+                .await?
+            {
+                return Err(user_exceeds_caller_permissions());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records a change to the roles of a user, comparing them with the roles held before.
+    ///
+    /// # Arguments
+    ///
+    /// * `audit` - Who made the change and where the request came from.
+    /// * `action` - The kind of change.
+    /// * `id` - The user ID.
+    /// * `before` - The snapshot taken by `user_roles_snapshot` before the change.
+    async fn record_user_roles_change(
+        &self,
+        audit: &AuditContext,
+        action: AuditAction,
+        id: Uuid,
+        before: Value,
+    ) {
+        let mut entry = AuditEntry::new(action, AuditEntity::UserRole, id).with_before(&before);
+        match self.user_roles_snapshot(id).await {
+            Ok(after) => entry = entry.with_after(&after),
+            Err(e) => {
+                tracing::error!(user_id = %id, "failed to read roles for the audit log: {}", e)
+            }
+        }
+
+        self.audit_service.record(audit, entry).await;
+    }
+
+    /// Records an employee lifecycle transition with its side effects.
+    ///
+    /// # Arguments
+    ///
+    /// * `audit` - Who made the change and where the request came from.
+    /// * `action` - `AuditAction::Deactivate` or `AuditAction::Reactivate`.
+    /// * `event` - The lifecycle event returned by the user repository.
+    async fn record_lifecycle_event(
+        &self,
+        audit: &AuditContext,
+        action: AuditAction,
+        event: &EmployeeLifecycleEventDTO,
+    ) {
+        let is_active = action == AuditAction::Reactivate;
+
+        let mut after = json!({ "is_active": is_active });
+        if let (Value::Object(after), Ok(Value::Object(side_effects))) =
+            (&mut after, serde_json::to_value(event))
+        {
+            after.extend(side_effects);
+        }
+
+        self.audit_service
+            .record(
+                audit,
+                AuditEntry::new(action, AuditEntity::User, event.employee_id)
+                    .with_before(&json!({ "is_active": !is_active }))
+                    .with_after(&after),
+            )
+            .await;
     }
 }
 
 impl UserAccessManagementService {
-    pub async fn register_user(&self, payload: CreateUserDTO) -> Response {
-        todo!()
+    /// Registers a new user.
+    ///
+    /// The password is hashed with argon2id and the user is persisted through the user
+    /// repository. The plaintext password never reaches the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The data transfer object containing user creation details.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 201 (Created) and the created user, or an error response.
+    pub async fn register_user(&self, payload: CreateUserDTO, audit: &AuditContext) -> Response {
+        let password = match hash_password(&payload.password) {
+            Ok(password) => password,
+            Err(e) => return e.into_response(),
+        };
+
+        let payload = CreateUserDTO {
+            password,
+            ..payload
+        };
+
+        match self
+            .repository_container
+            .user_repo
+            .create_user(payload)
+            .await
+        {
+            Ok(user) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Create, AuditEntity::User, user.id)
+                            .with_after(&user),
+                    )
+                    .await;
+                (StatusCode::CREATED, Json(user)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
     }
 
-    pub async fn login_user(&self, username: &str, password: &str) -> Response {
-        todo!()
+    /// Logs a user in.
+    ///
+    /// The password is verified against the stored argon2 hash and, on success, a signed
+    /// access token and a refresh token are issued.
+    ///
+    /// Failed logins are counted per username and per IP address. Every attempt is counted
+    /// before the password is checked and taken back if it succeeds, so parallel guesses
+    /// cannot exceed the threshold. Once either count reaches its threshold, logins for it
+    /// are rejected until the lockout ends, without checking the password. A successful login
+    /// clears the count of the username.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `password` - The plaintext password of the user.
+    /// * `ip_address` - The IP address the login came from, if known.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the issued tokens, 401 (Unauthorized), or
+    /// 429 (Too Many Requests) with a `Retry-After` header while locked out.
+    pub async fn login_user(
+        &self,
+        username: &str,
+        password: &str,
+        ip_address: Option<IpAddr>,
+    ) -> Response {
+        let throttle_keys = self.login_throttle_keys(username, ip_address);
+
+        let attempts = match self.count_login_attempt(&throttle_keys).await {
+            Ok(attempts) => attempts,
+            Err(e) => return e.into_response(),
+        };
+
+        // Attempts made while a key is locked out are not counted, and are rejected unchecked.
+        let locked_until = attempts
+            .iter()
+            .filter(|attempt| !attempt.counted)
+            .filter_map(|attempt| attempt.locked_until)
+            .max();
+        if let Some(locked_until) = locked_until {
+            self.metrics.record(DomainEvent::FailedLogin);
+            self.log_lockouts(username, ip_address, &throttle_keys, &attempts);
+            return login_locked(locked_until);
+        }
+
+        let user_id = match self.authenticate(username, password).await {
+            Ok(user_id) => user_id,
+            Err(e) => {
+                if let AppError::Unauthorized(_) = e {
+                    self.metrics.record(DomainEvent::FailedLogin);
+                    self.log_lockouts(username, ip_address, &throttle_keys, &attempts);
+                }
+                return e.into_response();
+            }
+        };
+
+        if let Err(e) = self.forgive_login_attempt(&throttle_keys, &attempts).await {
+            return e.into_response();
+        }
+
+        match self.issue_tokens(user_id).await {
+            Ok(tokens) => {
+                self.metrics.record(DomainEvent::Login);
+                (StatusCode::OK, Json(tokens)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
     }
 
-    pub async fn logout_user(&self, username: &str) -> Response {
-        todo!()
+    /// Lifts the login lockout of a username, an IP address, or both.
+    ///
+    /// The failed logins counted against them are forgotten as well. Every lifted lockout is
+    /// recorded in the audit log.
+    ///
+    /// # Arguments
+    ///
+    /// * `payload` - The data transfer object naming what to unlock.
+    /// * `audit` - Who is lifting the lockout and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found) if nothing was
+    /// locked out.
+    pub async fn unlock_login(&self, payload: UnlockLoginDTO, audit: &AuditContext) -> Response {
+        let login_throttle_repo = &self.repository_container.login_throttle_repo;
+        let keys = payload
+            .username
+            .iter()
+            .map(|username| (LoginThrottleScope::Username, username.clone()))
+            .chain(
+                payload
+                    .ip_address
+                    .map(|ip_address| (LoginThrottleScope::Ip, ip_address.to_string())),
+            );
+
+        let mut unlocked = false;
+        for (scope, key) in keys {
+            let throttle = match login_throttle_repo.clear(scope, &key).await {
+                Ok(Some(throttle)) => throttle,
+                Ok(None) => continue,
+                Err(e) => return e.into_response(),
+            };
+
+            if throttle
+                .locked_until
+                .is_some_and(|locked_until| locked_until > Utc::now())
+            {
+                unlocked = true;
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(
+                            AuditAction::Delete,
+                            AuditEntity::LoginLockout,
+                            format!("{}:{}", scope.as_str(), key),
+                        )
+                        .with_before(&throttle),
+                    )
+                    .await;
+            }
+        }
+
+        if !unlocked {
+            return AppError::not_found("lockout_not_found", "No matching login is locked out")
+                .into_response();
+        }
+
+        StatusCode::NO_CONTENT.into_response()
+    }
+
+    /// Refreshes a session.
+    ///
+    /// # Arguments
+    ///
+    /// * `refresh_token` - The refresh token of the session to refresh.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the new tokens, or 401 (Unauthorized).
+    pub async fn refresh_session(&self, refresh_token: &str) -> Response {
+        match self.rotate_refresh_token(refresh_token).await {
+            Ok(tokens) => (StatusCode::OK, Json(tokens)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Logs a user out by revoking the session the refresh token belongs to.
+    ///
+    /// # Arguments
+    ///
+    /// * `refresh_token` - The refresh token of the session to revoke.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 401 (Unauthorized).
+    pub async fn logout_user(&self, refresh_token: &str) -> Response {
+        match self
+            .repository_container
+            .session_repo
+            .revoke_active_session(&hash_refresh_token(refresh_token))
+            .await
+        {
+            Ok(_) => StatusCode::NO_CONTENT.into_response(),
+            Err(AppError::NotFound(_)) => invalid_refresh_token().into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves a user by ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the user, or 404 (Not Found).
+    pub async fn get_user_by_id(&self, id: Uuid) -> Response {
+        match self.repository_container.user_repo.get_user_by_id(id).await {
+            Ok(user) => (StatusCode::OK, Json(user)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves a page of users.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The pagination, sort and filter parameters.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the page of users, or 400 (Bad Request) if the
+    /// cursor or the sort field is invalid.
+    pub async fn get_users(&self, query: ListQuery) -> Response {
+        match self
+            .repository_container
+            .user_repo
+            .get_all_users(&query)
+            .await
+        {
+            Ok(users) => (StatusCode::OK, Json(users)).into_response(),
+            Err(e) => e.into_response(),
+        }
     }
 
-    pub async fn get_user(&self, username: &str) -> Response {
-        todo!()
+    /// Updates a user.
+    ///
+    /// The payload is validated and a new password, if present, is hashed with argon2id
+    /// before it is persisted.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `payload` - The data transfer object containing user update details.
+    /// * `caller_id` - The UUID of the user making the change.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the updated user, 403 (Forbidden) if the
+    /// username or password of a user allowed more than the caller would change, or another
+    /// error response.
+    pub async fn update_user(
+        &self,
+        id: Uuid,
+        payload: UpdateUserDTO,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Err(e) = self
+            .ensure_can_change_credentials(caller_id, id, &payload)
+            .await
+        {
+            return e.into_response();
+        }
+
+        let payload = match self.prepare_user_update(payload) {
+            Ok(payload) => payload,
+            Err(e) => return e.into_response(),
+        };
+        let password_changed = payload.password.is_some();
+
+        let user_repo = &self.repository_container.user_repo;
+
+        let before = match user_repo.get_user_by_id(id).await {
+            Ok(user) => user,
+            Err(e) => return e.into_response(),
+        };
+
+        match user_repo.update_user(id, payload).await {
+            Ok(user) => {
+                let mut entry = AuditEntry::new(AuditAction::Update, AuditEntity::User, id)
+                    .with_before(&before)
+                    .with_after(&user);
+                if password_changed {
+                    entry = entry.with_changed_secret("password");
+                }
+                self.audit_service.record(audit, entry).await;
+                (StatusCode::OK, Json(user)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
     }
 
-    pub async fn get_employees(&self) -> Response {
-        todo!()
+    /// Soft deletes a user. The user can be restored until it is purged.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found).
+    pub async fn delete_user(&self, id: Uuid, audit: &AuditContext) -> Response {
+        let user_repo = &self.repository_container.user_repo;
+
+        let before = match user_repo.get_user_by_id(id).await {
+            Ok(user) => user,
+            Err(e) => return e.into_response(),
+        };
+
+        match user_repo.delete_user(id).await {
+            Ok(()) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Delete, AuditEntity::User, id)
+                            .with_before(&before),
+                    )
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
     }
 
+    /// Restores a soft deleted user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the restored user, or 404 (Not Found).
+    pub async fn restore_user(&self, id: Uuid, audit: &AuditContext) -> Response {
+        match self.repository_container.user_repo.restore_user(id).await {
+            Ok(user) => {
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Restore, AuditEntity::User, id)
+                            .with_after(&user),
+                    )
+                    .await;
+                (StatusCode::OK, Json(user)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Permanently removes a soft deleted user, handing their stores to a new owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `query` - The user taking over the stores of the purged user.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), 404 (Not Found) if the user is not soft deleted,
+    /// 409 (Conflict) if the user owns stores and no new owner is given, or 422 (Unprocessable Entity)
+    /// if the new owner is not an existing user.
+    pub async fn purge_user(
+        &self,
+        id: Uuid,
+        query: PurgeUserQuery,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Some(new_owner_id) = query.new_owner_id {
+            if new_owner_id == id {
+                return AppError::UnprocessableEntity(
+                    ErrorDetail::new(
+                        "invalid_new_owner",
+                        "The stores cannot be reassigned to the purged user",
+                    )
+                    .with_field("new_owner_id", "must differ from the purged user"),
+                )
+                .into_response();
+            }
+
+            match self
+                .repository_container
+                .user_repo
+                .get_user_by_id(new_owner_id)
+                .await
+            {
+                Ok(_) => {}
+                Err(AppError::NotFound(_)) => {
+                    return AppError::UnprocessableEntity(
+                        ErrorDetail::new("invalid_new_owner", "The new owner does not exist")
+                            .with_field("new_owner_id", "does not reference an existing user"),
+                    )
+                    .into_response()
+                }
+                Err(e) => return e.into_response(),
+            }
+        }
+
+        match self
+            .repository_container
+            .user_repo
+            .purge_user(id, query.new_owner_id)
+            .await
+        {
+            Ok(reassigned_stores) => {
+                tracing::info!(user_id = %id, reassigned_stores, "user purged");
+                self.audit_service
+                    .record(
+                        audit,
+                        AuditEntry::new(AuditAction::Purge, AuditEntity::User, id).with_after(
+                            &json!({
+                                "new_owner_id": query.new_owner_id,
+                                "reassigned_stores": reassigned_stores,
+                            }),
+                        ),
+                    )
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the roles held by a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the roles, or 404 (Not Found) if the user does not exist.
+    pub async fn get_user_roles(&self, id: Uuid) -> Response {
+        if let Err(e) = self.repository_container.user_repo.get_user_by_id(id).await {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .user_role_repo
+            .list_roles_for_user(id)
+            .await
+        {
+            Ok(roles) => (StatusCode::OK, Json(roles)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Grants a role to a user, globally or within a store.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `payload` - The role to grant and the store it applies to.
+    /// * `caller_id` - The UUID of the user granting the role.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 201 (Created), 403 (Forbidden) if the role grants
+    /// permissions the caller does not hold, 404 (Not Found) if the user or the role does not
+    /// exist, or 409 (Conflict) if the user already holds the role in that scope.
+    pub async fn assign_user_role(
+        &self,
+        id: Uuid,
+        payload: AssignUserRoleDTO,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Err(e) = self.repository_container.user_repo.get_user_by_id(id).await {
+            return e.into_response();
+        }
+
+        if let Err(e) = self
+            .repository_container
+            .role_repo
+            .get_role_by_id(payload.role_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        if let Err(e) = self
+            .ensure_can_grant(caller_id, payload.role_id, payload.store_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        let before = match self.user_roles_snapshot(id).await {
+            Ok(before) => before,
+            Err(e) => return e.into_response(),
+        };
+
+        match self
+            .repository_container
+            .user_role_repo
+            .add_user_role(id, payload.role_id, payload.store_id)
+            .await
+        {
+            Ok(user_role) => {
+                self.record_user_roles_change(audit, AuditAction::Create, id, before)
+                    .await;
+                (StatusCode::CREATED, Json(user_role)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Replaces one of a user's roles with another role in the same scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `role_id` - The ID of the role being replaced.
+    /// * `payload` - The replacing role and the store the assignment applies to.
+    /// * `caller_id` - The UUID of the user granting the replacing role.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the resulting roles of the user, 403 (Forbidden)
+    /// if the replacing role grants permissions the caller does not hold, or 404 (Not Found)
+    /// if the user or the replacing role does not exist, or the user does not hold `role_id` in that scope.
+    pub async fn replace_user_role(
+        &self,
+        id: Uuid,
+        role_id: i32,
+        payload: ReplaceUserRoleDTO,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Err(e) = self.repository_container.user_repo.get_user_by_id(id).await {
+            return e.into_response();
+        }
+
+        if let Err(e) = self
+            .repository_container
+            .role_repo
+            .get_role_by_id(payload.new_role_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        if let Err(e) = self
+            .ensure_can_grant(caller_id, payload.new_role_id, payload.store_id)
+            .await
+        {
+            return e.into_response();
+        }
+
+        let before = match self.user_roles_snapshot(id).await {
+            Ok(before) => before,
+            Err(e) => return e.into_response(),
+        };
+
+        match self
+            .repository_container
+            .user_role_repo
+            .update_user_role(id, role_id, payload.new_role_id, payload.store_id)
+            .await
+        {
+            Ok(roles) => {
+                self.record_user_roles_change(audit, AuditAction::Update, id, before)
+                    .await;
+                (StatusCode::OK, Json(roles)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Sets the roles of a user to exactly the given assignments.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `payload` - The role assignments the user should hold.
+    /// * `caller_id` - The UUID of the user granting the roles.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the resulting roles of the user, 403 (Forbidden)
+    /// if a role the user does not hold yet grants permissions the caller does not hold, or
+    /// 404 (Not Found) if the user or any of the roles does not exist.
+    pub async fn set_user_roles(
+        &self,
+        id: Uuid,
+        payload: SetUserRolesDTO,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Err(e) = self.repository_container.user_repo.get_user_by_id(id).await {
+            return e.into_response();
+        }
+
+        let current_roles = match self
+            .repository_container
+            .user_role_repo
+            .list_roles_for_user(id)
+            .await
+        {
+            Ok(current_roles) => current_roles,
+            Err(e) => return e.into_response(),
+        };
+
+        for role in &payload.roles {
+            if let Err(e) = self
+                .repository_container
+                .role_repo
+                .get_role_by_id(role.role_id)
+                .await
+            {
+                return e.into_response();
+            }
+
+            // Assignments the user keeps were checked when they were granted.
+            let is_new = !current_roles.iter().any(|current| {
+                current.role_id == role.role_id && current.store_id == role.store_id
+            });
+            if is_new {
+                if let Err(e) = self
+                    .ensure_can_grant(caller_id, role.role_id, role.store_id)
+                    .await
+                {
+                    return e.into_response();
+                }
+            }
+        }
+
+        let before = match self.user_roles_snapshot(id).await {
+            Ok(before) => before,
+            Err(e) => return e.into_response(),
+        };
+
+        match self
+            .repository_container
+            .user_role_repo
+            .set_user_roles(id, &payload.roles)
+            .await
+        {
+            Ok(roles) => {
+                self.record_user_roles_change(audit, AuditAction::Update, id, before)
+                    .await;
+                (StatusCode::OK, Json(roles)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Revokes a role from a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID.
+    /// * `role_id` - The ID of the role to revoke.
+    /// * `store_id` - The store the role was granted in, or `None` for a global assignment.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found) if the user does not
+    /// hold the role in that scope.
+    pub async fn revoke_user_role(
+        &self,
+        id: Uuid,
+        role_id: i32,
+        store_id: Option<i32>,
+        audit: &AuditContext,
+    ) -> Response {
+        let before = match self.user_roles_snapshot(id).await {
+            Ok(before) => before,
+            Err(e) => return e.into_response(),
+        };
+
+        match self
+            .repository_container
+            .user_role_repo
+            .delete_user_role(id, role_id, store_id)
+            .await
+        {
+            Ok(()) => {
+                self.record_user_roles_change(audit, AuditAction::Delete, id, before)
+                    .await;
+                StatusCode::NO_CONTENT.into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the team of a manager: every user reporting to them, directly or indirectly.
+    ///
+    /// # Arguments
+    ///
+    /// * `manager_id` - The UUID of the manager, usually the authenticated user.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the team ordered by reporting depth.
+    pub async fn get_employees(&self, manager_id: Uuid) -> Response {
+        match self
+            .repository_container
+            .user_hierarchy_repo
+            .get_subtree(manager_id)
+            .await
+        {
+            Ok(employees) => (StatusCode::OK, Json(employees)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Retrieves an employee by ID, whether active or not.
+    ///
+    /// # Arguments
+    ///
+    /// * `employee_id` - The user ID of the employee.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the employee, or 404 (Not Found).
     pub async fn get_employee_by_id(&self, employee_id: Uuid) -> Response {
-        todo!()
+        match self
+            .repository_container
+            .user_repo
+            .get_user_entity_by_id(employee_id)
+            .await
+        {
+            Ok(user) => (StatusCode::OK, Json(EmployeeResponseDTO::from(user))).into_response(),
+            Err(e) => e.into_response(),
+        }
     }
 
-    pub async fn update_employee(&self, employee_id: Uuid, payload: UpdateUserDTO) -> Response {
-        todo!()
+    /// Updates an employee.
+    ///
+    /// The payload is validated and a new password, if present, is hashed with argon2id
+    /// before it is persisted.
+    ///
+    /// # Arguments
+    ///
+    /// * `employee_id` - The user ID of the employee.
+    /// * `payload` - The data transfer object containing user update details.
+    /// * `caller_id` - The UUID of the user making the change.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the updated employee, 403 (Forbidden) if the
+    /// username or password of a user allowed more than the caller would change, or another
+    /// error response.
+    pub async fn update_employee(
+        &self,
+        employee_id: Uuid,
+        payload: UpdateUserDTO,
+        caller_id: Uuid,
+        audit: &AuditContext,
+    ) -> Response {
+        if let Err(e) = self
+            .ensure_can_change_credentials(caller_id, employee_id, &payload)
+            .await
+        {
+            return e.into_response();
+        }
+
+        let payload = match self.prepare_user_update(payload) {
+            Ok(payload) => payload,
+            Err(e) => return e.into_response(),
+        };
+        let password_changed = payload.password.is_some();
+
+        let user_repo = &self.repository_container.user_repo;
+
+        let before = match user_repo.get_user_entity_by_id(employee_id).await {
+            Ok(user) => EmployeeResponseDTO::from(user),
+            Err(e) => return e.into_response(),
+        };
+
+        if let Err(e) = user_repo.update_user(employee_id, payload).await {
+            return e.into_response();
+        }
+
+        match user_repo.get_user_entity_by_id(employee_id).await {
+            Ok(user) => {
+                let employee = EmployeeResponseDTO::from(user);
+                let mut entry =
+                    AuditEntry::new(AuditAction::Update, AuditEntity::User, employee_id)
+                        .with_before(&before)
+                        .with_after(&employee);
+                if password_changed {
+                    entry = entry.with_changed_secret("password");
+                }
+                self.audit_service.record(audit, entry).await;
+                (StatusCode::OK, Json(employee)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Deactivates an employee.
+    ///
+    /// The employee can no longer authenticate: their sessions are revoked and their access
+    /// tokens are refused. They are removed from every store and from the reporting hierarchy,
+    /// and their direct reports move to their manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `employee_id` - The user ID of the employee.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the lifecycle event, 404 (Not Found), or
+    /// 409 (Conflict) if the employee is already inactive.
+    pub async fn deactivate_employee(&self, employee_id: Uuid, audit: &AuditContext) -> Response {
+        match self
+            .repository_container
+            .user_repo
+            .deactivate_user(employee_id)
+            .await
+        {
+            Ok(event) => {
+                tracing::info!(
+                    employee_id = %employee_id,
+                    revoked_sessions = event.revoked_sessions,
+                    removed_store_memberships = event.removed_store_memberships,
+                    reassigned_reports = event.reassigned_reports.len(),
+                    "employee deactivated"
+                );
+                self.record_lifecycle_event(audit, AuditAction::Deactivate, &event)
+                    .await;
+                (StatusCode::OK, Json(event)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Reactivates an employee, allowing them to authenticate again.
+    ///
+    /// # Arguments
+    ///
+    /// * `employee_id` - The user ID of the employee.
+    /// * `audit` - Who made the change and where the request came from.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the lifecycle event, 404 (Not Found), or
+    /// 409 (Conflict) if the employee is already active.
+    pub async fn reactive_employee(&self, employee_id: Uuid, audit: &AuditContext) -> Response {
+        match self
+            .repository_container
+            .user_repo
+            .reactivate_user(employee_id)
+            .await
+        {
+            Ok(event) => {
+                tracing::info!(employee_id = %employee_id, "employee reactivated");
+                self.record_lifecycle_event(audit, AuditAction::Reactivate, &event)
+                    .await;
+                (StatusCode::OK, Json(event)).into_response()
+            }
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{create_role, create_user, grant_role, json_body};
+    use sqlx::PgPool;
+
+    fn service(pool: &PgPool) -> UserAccessManagementService {
+        let repository_container = Arc::new(RepositoryContainer::new(pool.clone()));
+        let audit_service = Arc::new(AuditService::new(repository_container.clone()));
+
+        UserAccessManagementService::new(
+            repository_container,
+            Arc::new(AppConfig::for_tests()),
+            Arc::new(Metrics::new()),
+            audit_service,
+        )
+    }
+
+    async fn admin_role(pool: &PgPool) -> i32 {
+        sqlx::query_scalar("SELECT id FROM roles WHERE name = 'admin'")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn role_count(pool: &PgPool, user_id: Uuid) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM user_roles WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    /// Creates a caller holding a support role that may manage user roles but only read users,
+    /// and a clerk role that grants nothing beyond that.
+    async fn support_caller(pool: &PgPool) -> (Uuid, i32) {
+        let caller = create_user(pool, "support").await;
+        let support = create_role(
+            pool,
+            "support",
+            &[
+                ("users", true, false, false, false),
+                ("user_roles", true, true, false, true),
+            ],
+        )
+        .await;
+        let clerk = create_role(pool, "clerk", &[("users", true, false, false, false)]).await;
+        grant_role(pool, caller, support, None).await;
+        (caller, clerk)
+    }
+
+    #[sqlx::test]
+    async fn refresh_rotates_the_refresh_token(pool: PgPool) {
+        let service = service(&pool);
+        let alice = create_user(&pool, "alice").await;
+        let tokens = service.issue_tokens(alice).await.unwrap();
+
+        let response = service.refresh_session(&tokens.refresh_token).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let rotated = json_body(response).await["refresh_token"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_ne!(rotated, tokens.refresh_token);
+
+        let replayed = service.refresh_session(&tokens.refresh_token).await;
+        assert_eq!(replayed.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(json_body(replayed).await["code"], "invalid_refresh_token");
+        assert_eq!(
+            service.refresh_session(&rotated).await.status(),
+            StatusCode::OK
+        );
+    }
+
+    #[sqlx::test]
+    async fn refresh_token_is_redeemed_once_under_concurrency(pool: PgPool) {
+        let service = Arc::new(service(&pool));
+        let alice = create_user(&pool, "alice").await;
+        let tokens = service.issue_tokens(alice).await.unwrap();
+
+        let refreshes: Vec<_> = (0..6)
+            .map(|_| {
+                let service = service.clone();
+                let refresh_token = tokens.refresh_token.clone();
+                tokio::spawn(async move { service.refresh_session(&refresh_token).await.status() })
+            })
+            .collect();
+        let mut statuses = Vec::new();
+        for refresh in refreshes {
+            statuses.push(refresh.await.unwrap());
+        }
+
+        assert_eq!(
+            statuses
+                .iter()
+                .filter(|status| **status == StatusCode::OK)
+                .count(),
+            1
+        );
+        assert!(statuses
+            .iter()
+            .all(|status| *status == StatusCode::OK || *status == StatusCode::UNAUTHORIZED));
+    }
+
+    #[sqlx::test]
+    async fn logout_revokes_the_session(pool: PgPool) {
+        let service = service(&pool);
+        let alice = create_user(&pool, "alice").await;
+        let tokens = service.issue_tokens(alice).await.unwrap();
+
+        let logout = service.logout_user(&tokens.refresh_token).await;
+        assert_eq!(logout.status(), StatusCode::NO_CONTENT);
+
+        assert_eq!(
+            service
+                .refresh_session(&tokens.refresh_token)
+                .await
+                .status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            service.logout_user(&tokens.refresh_token).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[sqlx::test]
+    async fn refresh_is_refused_for_inactive_and_deleted_users(pool: PgPool) {
+        let service = service(&pool);
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        let alice_tokens = service.issue_tokens(alice).await.unwrap();
+        let bob_tokens = service.issue_tokens(bob).await.unwrap();
+        sqlx::query("UPDATE users SET is_active = FALSE WHERE id = $1")
+            .bind(alice)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE users SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(bob)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service
+                .refresh_session(&alice_tokens.refresh_token)
+                .await
+                .status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            service
+                .refresh_session(&bob_tokens.refresh_token)
+                .await
+                .status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[sqlx::test]
+    async fn assigning_a_role_beyond_the_caller_is_forbidden(pool: PgPool) {
+        let service = service(&pool);
+        let (caller, clerk) = support_caller(&pool).await;
+        let bob = create_user(&pool, "bob").await;
+        let admin = admin_role(&pool).await;
+
+        let escalation = service
+            .assign_user_role(
+                bob,
+                AssignUserRoleDTO {
+                    role_id: admin,
+                    store_id: None,
+                },
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(escalation.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            json_body(escalation).await["code"],
+            "role_exceeds_caller_permissions"
+        );
+        assert_eq!(role_count(&pool, bob).await, 0);
+
+        let within = service
+            .assign_user_role(
+                bob,
+                AssignUserRoleDTO {
+                    role_id: clerk,
+                    store_id: None,
+                },
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(within.status(), StatusCode::CREATED);
+        assert_eq!(role_count(&pool, bob).await, 1);
+    }
+
+    #[sqlx::test]
+    async fn setting_roles_with_an_escalating_role_changes_nothing(pool: PgPool) {
+        let service = service(&pool);
+        let (caller, clerk) = support_caller(&pool).await;
+        let bob = create_user(&pool, "bob").await;
+        let admin = admin_role(&pool).await;
+
+        let response = service
+            .set_user_roles(
+                bob,
+                SetUserRolesDTO {
+                    roles: vec![
+                        AssignUserRoleDTO {
+                            role_id: clerk,
+                            store_id: None,
+                        },
+                        AssignUserRoleDTO {
+                            role_id: admin,
+                            store_id: None,
+                        },
+                    ],
+                },
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(role_count(&pool, bob).await, 0);
+    }
+
+    fn credentials_update(username: Option<&str>, password: Option<&str>) -> UpdateUserDTO {
+        UpdateUserDTO {
+            username: username.map(str::to_string),
+            email: None,
+            password: password.map(str::to_string),
+        }
+    }
+
+    #[sqlx::test]
+    async fn credentials_of_a_more_powerful_user_cannot_be_changed(pool: PgPool) {
+        let service = service(&pool);
+        let caller = create_user(&pool, "user_admin").await;
+        let user_admin =
+            create_role(&pool, "user_admin", &[("users", true, true, true, true)]).await;
+        grant_role(&pool, caller, user_admin, None).await;
+        let alice = create_user(&pool, "alice").await;
+        grant_role(&pool, alice, admin_role(&pool).await, None).await;
+
+        for payload in [
+            credentials_update(None, Some("Correct-Horse-42")),
+            credentials_update(Some("mallory"), None),
+        ] {
+            let response = service
+                .update_user(alice, payload, caller, &AuditContext::default())
+                .await;
+            assert_eq!(response.status(), StatusCode::FORBIDDEN);
+            assert_eq!(
+                json_body(response).await["code"],
+                "user_exceeds_caller_permissions"
+            );
+        }
+
+        let employee = service
+            .update_employee(
+                alice,
+                credentials_update(None, Some("Correct-Horse-42")),
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(employee.status(), StatusCode::FORBIDDEN);
+
+        let email_only = service
+            .update_user(
+                alice,
+                UpdateUserDTO {
+                    email: Some("alice@example.org".to_string()),
+                    ..credentials_update(None, None)
+                },
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(email_only.status(), StatusCode::OK);
     }
 
-    pub async fn deactivate_employee(&self, employee_id: Uuid) -> Response {
-        todo!()
+    #[sqlx::test]
+    async fn credentials_of_a_user_within_the_caller_can_be_changed(pool: PgPool) {
+        let service = service(&pool);
+        let (caller, clerk) = support_caller(&pool).await;
+        let bob = create_user(&pool, "bob").await;
+        grant_role(&pool, bob, clerk, None).await;
+
+        let response = service
+            .update_user(
+                bob,
+                credentials_update(Some("robert"), Some("Correct-Horse-42")),
+                caller,
+                &AuditContext::default(),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["username"], "robert");
     }
 
-    pub async fn reactive_employee(&self, employee_id: Uuid) -> Response {
-        todo!()
+    #[sqlx::test]
+    async fn authenticate_reports_every_failure_the_same_way(pool: PgPool) {
+        let service = service(&pool);
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        sqlx::query("UPDATE users SET password = $1")
+            .bind(hash_password("Correct-Horse-42").unwrap())
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE users SET is_active = FALSE WHERE id = $1")
+            .bind(bob)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service
+                .authenticate("alice", "Correct-Horse-42")
+                .await
+                .unwrap(),
+            alice
+        );
+        for (username, password) in [
+            ("alice", "Wrong-Horse-42"),
+            ("bob", "Correct-Horse-42"),
+            ("nobody", "Correct-Horse-42"),
+        ] {
+            let result = service.authenticate(username, password).await;
+            assert!(
+                matches!(&result, Err(AppError::Unauthorized(detail)) if detail.code == "invalid_credentials"),
+                "{username}: {result:?}"
+            );
+        }
     }
 }
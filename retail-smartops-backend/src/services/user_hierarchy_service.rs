@@ -0,0 +1,212 @@
+use crate::errors::{AppError, ErrorDetail};
+use crate::models::user_hierarchy::SetManagerDTO;
+use crate::repositories::RepositoryContainer;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct UserHierarchyService {
+    repository_container: Arc<RepositoryContainer>,
+}
+
+impl UserHierarchyService {
+    pub fn new(repository_container: Arc<RepositoryContainer>) -> Self {
+        Self {
+            repository_container,
+        }
+    }
+
+    /// Checks that a user exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The UUID of the user.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), AppError>` - `Ok(())` if the user exists, `AppError::NotFound` otherwise.
+    async fn check_user_exists(&self, id: Uuid) -> Result<(), AppError> {
+        self.repository_container
+            .user_repo
+            .get_user_by_id(id)
+            .await
+            .map(|_| ())
+    }
+}
+
+impl UserHierarchyService {
+    /// Makes a user report to a manager.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the reporting line, 404 (Not Found) if the user
+    /// does not exist, 422 (Unprocessable Entity) if the manager does not exist, is deleted or is
+    /// deactivated, or 409 (Conflict) if the assignment would create a reporting loop.
+    pub async fn set_manager(&self, id: Uuid, payload: SetManagerDTO) -> Response {
+        if let Err(e) = self.check_user_exists(id).await {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .user_repo
+            .get_user_entity_by_id(payload.manager_id)
+            .await
+        {
+            Ok(manager) if manager.is_active => {}
+            Ok(_) => {
+                return AppError::UnprocessableEntity(
+                    ErrorDetail::new("manager_inactive", "The manager is deactivated")
+                        .with_field("manager_id", "references a deactivated user"),
+                )
+                .into_response()
+            }
+            Err(AppError::NotFound(_)) => {
+                return AppError::UnprocessableEntity(
+                    ErrorDetail::new("manager_not_found", "The manager does not exist")
+                        .with_field("manager_id", "does not reference an existing user"),
+                )
+                .into_response()
+            }
+            Err(e) => return e.into_response(),
+        }
+
+        match self
+            .repository_container
+            .user_hierarchy_repo
+            .set_manager(id, payload.manager_id)
+            .await
+        {
+            Ok(user_hierarchy) => (StatusCode::OK, Json(user_hierarchy)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Removes the manager of a user.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 204 (No Content), or 404 (Not Found) if the user has no manager.
+    pub async fn remove_manager(&self, id: Uuid) -> Response {
+        match self
+            .repository_container
+            .user_hierarchy_repo
+            .remove_manager(id)
+            .await
+        {
+            Ok(()) => StatusCode::NO_CONTENT.into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the users reporting directly to a user.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the direct reports, or 404 (Not Found).
+    pub async fn get_direct_reports(&self, id: Uuid) -> Response {
+        if let Err(e) = self.check_user_exists(id).await {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .user_hierarchy_repo
+            .get_direct_reports(id)
+            .await
+        {
+            Ok(reports) => (StatusCode::OK, Json(reports)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists every user reporting to a user, directly or indirectly.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the subtree, or 404 (Not Found).
+    pub async fn get_subtree(&self, id: Uuid) -> Response {
+        if let Err(e) = self.check_user_exists(id).await {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .user_hierarchy_repo
+            .get_subtree(id)
+            .await
+        {
+            Ok(subtree) => (StatusCode::OK, Json(subtree)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    /// Lists the managers above a user, starting with the direct manager.
+    ///
+    /// # Returns
+    ///
+    /// A `Response` with status code 200 (OK) and the management chain, or 404 (Not Found).
+    pub async fn get_management_chain(&self, id: Uuid) -> Response {
+        if let Err(e) = self.check_user_exists(id).await {
+            return e.into_response();
+        }
+
+        match self
+            .repository_container
+            .user_hierarchy_repo
+            .get_management_chain(id)
+            .await
+        {
+            Ok(chain) => (StatusCode::OK, Json(chain)).into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{create_user, json_body};
+    use sqlx::PgPool;
+
+    async fn set_manager(pool: &PgPool, id: Uuid, manager_id: Uuid) -> Response {
+        UserHierarchyService::new(Arc::new(RepositoryContainer::new(pool.clone())))
+            .set_manager(id, SetManagerDTO { manager_id })
+            .await
+    }
+
+    #[sqlx::test]
+    async fn manager_must_be_active(pool: PgPool) {
+        let alice = create_user(&pool, "alice").await;
+        let bob = create_user(&pool, "bob").await;
+        let carol = create_user(&pool, "carol").await;
+        sqlx::query("UPDATE users SET is_active = FALSE WHERE id = $1")
+            .bind(bob)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE users SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(carol)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let inactive = set_manager(&pool, alice, bob).await;
+        assert_eq!(inactive.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = json_body(inactive).await;
+        assert_eq!(body["code"], "manager_inactive");
+        assert_eq!(body["errors"][0]["field"], "manager_id");
+
+        let deleted = set_manager(&pool, alice, carol).await;
+        assert_eq!(deleted.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(json_body(deleted).await["code"], "manager_not_found");
+
+        let reports_to: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM user_hierarchy")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(reports_to, 0);
+    }
+}
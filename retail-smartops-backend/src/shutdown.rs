@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Broadcasts the shutdown of the application.
+///
+/// The HTTP server and its drain deadline each take a `ShutdownSignal` from `subscribe` and
+/// wait on it; `trigger` wakes all of them at once.
+#[derive(Clone)]
+pub struct Shutdown {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// Signals every subscriber that the application is shutting down.
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Creates a signal that completes once the shutdown is triggered.
+    ///
+    /// # Returns
+    ///
+    /// A `ShutdownSignal`, already complete if the shutdown was triggered before.
+    pub fn subscribe(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            receiver: self.sender.subscribe(),
+        }
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A subscription to the shutdown of the application.
+pub struct ShutdownSignal {
+    receiver: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal {
+    /// Waits until the shutdown is triggered.
+    pub async fn recv(mut self) {
+        // The sender lives as long as any `Shutdown`, so an error means nobody can trigger
+        // the shutdown anymore and waiting is over as well.
+        let _ = self
+            .receiver
+            .wait_for(|is_shutting_down| *is_shutting_down)
+            .await;
+    }
+}
+
+/// Waits until the process is asked to stop with Ctrl-C or, on Unix, with SIGTERM.
+///
+/// # Panics
+///
+/// This function will panic if the signal handlers cannot be installed.
+pub async fn termination_requested() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install the Ctrl-C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install the SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
@@ -0,0 +1,151 @@
+//! Fixtures shared by the database-backed tests.
+//!
+//! The helpers insert rows with plain SQL, bypassing the services, so a test only exercises the
+//! code it is about. They panic on any database error.
+
+use crate::auth::token::generate_access_token;
+use crate::config::AppConfig;
+use crate::db::DbService;
+use crate::repositories::RepositoryContainer;
+use crate::routes::create_app_routes;
+use crate::AppState;
+use axum::body::Body;
+use axum::http::{header, Method, Request};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use serde_json::Value;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tower::Service;
+use uuid::Uuid;
+
+/// Creates an active user whose email is derived from the username. The password hash is not a
+/// valid hash, so the user cannot log in.
+pub async fn create_user(pool: &PgPool, username: &str) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO users (username, password, email) VALUES ($1, 'unused', $1 || '@example.com') \
+         RETURNING id",
+    )
+    .bind(username)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+/// Creates a role holding one permission per `(entity_name, read, write, delete, update)`.
+pub async fn create_role(
+    pool: &PgPool,
+    name: &str,
+    permissions: &[(&str, bool, bool, bool, bool)],
+) -> i32 {
+    let role_id = sqlx::query_scalar("INSERT INTO roles (name) VALUES ($1) RETURNING id")
+        .bind(name)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    for (entity_name, can_read, can_write, can_delete, can_update) in permissions {
+        sqlx::query(
+            "WITH permission AS (
+                 INSERT INTO permissions (entity_name, can_read, can_write, can_delete, can_update)
+                 VALUES ($2, $3, $4, $5, $6)
+                 RETURNING id)
+             INSERT INTO role_permissions (role_id, permission_id)
+             SELECT $1, id FROM permission",
+        )
+        .bind(role_id)
+        .bind(entity_name)
+        .bind(can_read)
+        .bind(can_write)
+        .bind(can_delete)
+        .bind(can_update)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+    role_id
+}
+
+/// Grants a role to a user, globally or in a store.
+pub async fn grant_role(pool: &PgPool, user_id: Uuid, role_id: i32, store_id: Option<i32>) {
+    sqlx::query("INSERT INTO user_roles (user_id, role_id, store_id) VALUES ($1, $2, $3)")
+        .bind(user_id)
+        .bind(role_id)
+        .bind(store_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Creates a store owned by a user.
+pub async fn create_store(pool: &PgPool, owner_id: Uuid) -> i32 {
+    sqlx::query_scalar(
+        "INSERT INTO stores (store_name, country, state, city, street, zip, owner_id) \
+         VALUES ('Main', 'US', 'CA', 'Fresno', '1 Main St', '93701', $1) RETURNING store_id",
+    )
+    .bind(owner_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+/// Makes a user a member of a store, with no start or end date.
+pub async fn add_store_user(pool: &PgPool, store_id: i32, user_id: Uuid) {
+    sqlx::query("INSERT INTO store_users (store_id, user_id) VALUES ($1, $2)")
+        .bind(store_id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Reads the JSON body of a response.
+pub async fn json_body(response: Response) -> Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+/// Builds the whole application, routes and middleware included, on top of a test pool.
+pub fn app(pool: &PgPool) -> Router {
+    let app_state = AppState::new(
+        AppConfig::for_tests(),
+        Arc::new(DbService::from_pool(pool.clone())),
+        RepositoryContainer::new(pool.clone()),
+    );
+    create_app_routes(app_state)
+}
+
+/// Sends a request to the application, authenticated as `user_id` when one is given.
+pub async fn send(
+    app: &Router,
+    method: Method,
+    uri: &str,
+    user_id: Option<Uuid>,
+    body: Option<Value>,
+) -> Response {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(user_id) = user_id {
+        let config = AppConfig::for_tests();
+        let access_token = generate_access_token(
+            user_id,
+            config.get_jwt_secret(),
+            config.get_access_token_ttl(),
+        )
+        .unwrap();
+        request = request.header(header::AUTHORIZATION, format!("Bearer {access_token}"));
+    }
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+
+    match app.clone().call(request).await {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    }
+    .into_response()
+}
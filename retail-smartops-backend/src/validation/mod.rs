@@ -0,0 +1,382 @@
+use crate::errors::{AppError, FieldError};
+use axum::async_trait;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::extract::{FromRequest, FromRequestParts, Path, Query, Request};
+use axum::http::request::Parts;
+use axum::Json;
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+/// The shortest password accepted for a user.
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// The longest password accepted for a user. Hashing is expensive, so the input is bounded.
+const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// A JSON request body that is validated before the handler runs.
+///
+/// Works like `axum::Json`, but additionally runs the `Validate` rules declared on the payload
+/// type. A body that is not valid JSON is rejected with 400 (Bad Request); a body that does not
+/// match the payload type or breaks one of its rules is rejected with 422 (Unprocessable Entity)
+/// listing the offending fields.
+///
+/// # Example
+///
+/// ```ignore
+/// pub async fn create_role(
+///     State(app_state): State<AppState>,
+///     ValidatedJson(payload): ValidatedJson<CreateRoleDTO>,
+/// ) -> Response {
+///     ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(payload) = Json::<T>::from_request(request, state)
+            .await
+            .map_err(json_rejection_to_error)?;
+
+        payload
+            .validate()
+            .map_err(|errors| AppError::validation(field_errors(&errors)))?;
+
+        Ok(ValidatedJson(payload))
+    }
+}
+
+/// Path parameters that are rejected as an `AppError`.
+///
+/// Works like `axum::extract::Path`, but a parameter that cannot be parsed, e.g. a malformed
+/// UUID, is rejected with a 400 (Bad Request) problem instead of a plain-text body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedPath<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(params) = Path::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(path_rejection_to_error)?;
+
+        Ok(ValidatedPath(params))
+    }
+}
+
+/// Query parameters that are rejected as an `AppError`.
+///
+/// Works like `axum::extract::Query`, but a query string that does not match the parameter
+/// type is rejected with a 400 (Bad Request) problem instead of a plain-text body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidatedQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<T>::from_request_parts(parts, state)
+            .await
+            .map_err(|e: QueryRejection| AppError::bad_request("invalid_query", e.body_text()))?;
+
+        Ok(ValidatedQuery(params))
+    }
+}
+
+/// Maps a rejection of the path extractor to an `AppError`.
+fn path_rejection_to_error(rejection: PathRejection) -> AppError {
+    match rejection {
+        PathRejection::FailedToDeserializePathParams(e) => {
+            AppError::bad_request("invalid_path", e.body_text())
+        }
+        e => AppError::InternalServerError(e.body_text()),
+    }
+}
+
+/// Maps a rejection of the JSON extractor to an `AppError`.
+fn json_rejection_to_error(rejection: JsonRejection) -> AppError {
+    match rejection {
+        JsonRejection::JsonDataError(e) => {
+            AppError::unprocessable("invalid_payload", e.body_text())
+        }
+        JsonRejection::JsonSyntaxError(e) => AppError::bad_request("invalid_json", e.body_text()),
+        JsonRejection::MissingJsonContentType(e) => {
+            AppError::bad_request("unsupported_content_type", e.body_text())
+        }
+        e => AppError::bad_request("invalid_body", e.body_text()),
+    }
+}
+
+/// Flattens validation errors into field errors, sorted by field.
+///
+/// Errors of nested payloads are reported with dotted paths, e.g. `address.zip`, and errors
+/// of list items with their index, e.g. `roles[1].role_id`. Errors raised by struct-level
+/// rules are reported under the field named by the code of the error.
+///
+/// # Arguments
+///
+/// * `errors` - The errors returned by `Validate::validate`.
+///
+/// # Returns
+///
+/// A `Vec<FieldError>` with one entry per broken rule.
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut field_errors = Vec::new();
+    collect_field_errors(errors, None, &mut field_errors);
+    field_errors.sort_by(|a, b| a.field.cmp(&b.field));
+    field_errors
+}
+
+fn collect_field_errors(
+    errors: &ValidationErrors,
+    prefix: Option<&str>,
+    field_errors: &mut Vec<FieldError>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = match prefix {
+            Some(prefix) => format!("{prefix}.{field}"),
+            None => field.to_string(),
+        };
+
+        match kind {
+            // Struct-level rules name the field they are about through their code.
+            ValidationErrorsKind::Field(errors) if field == "__all__" => {
+                field_errors.extend(errors.iter().map(|error| {
+                    let field = match prefix {
+                        Some(prefix) => format!("{prefix}.{}", error.code),
+                        None => error.code.to_string(),
+                    };
+                    FieldError::new(field, error_message(error))
+                }));
+            }
+            ValidationErrorsKind::Field(errors) => {
+                field_errors.extend(
+                    errors
+                        .iter()
+                        .map(|error| FieldError::new(&path, error_message(error))),
+                );
+            }
+            ValidationErrorsKind::Struct(errors) => {
+                collect_field_errors(errors, Some(&path), field_errors);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_field_errors(errors, Some(&format!("{path}[{index}]")), field_errors);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the message of a validation error, falling back to its code.
+fn error_message(error: &ValidationError) -> String {
+    match &error.message {
+        Some(message) => message.to_string(),
+        None => format!("is invalid ({})", error.code),
+    }
+}
+
+/// Checks that a username only uses letters, digits, dots, underscores and hyphens.
+pub fn validate_username(username: &str) -> Result<(), ValidationError> {
+    let is_valid = username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+
+    if !is_valid {
+        return Err(ValidationError::new("username_charset").with_message(
+            "may only contain letters, digits, dots, underscores and hyphens".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that a password is long enough and mixes letters with digits.
+pub fn validate_password(password: &str) -> Result<(), ValidationError> {
+    let length = password.chars().count();
+    if !(MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&length) {
+        return Err(ValidationError::new("password_length").with_message(
+            format!("must be between {MIN_PASSWORD_LENGTH} and {MAX_PASSWORD_LENGTH} characters")
+                .into(),
+        ));
+    }
+
+    let has_letter = password.chars().any(char::is_alphabetic);
+    let has_digit = password.chars().any(|c| c.is_ascii_digit());
+    if !(has_letter && has_digit) {
+        return Err(ValidationError::new("password_strength")
+            .with_message("must contain at least one letter and one digit".into()));
+    }
+
+    Ok(())
+}
+
+/// Checks that a required text field is not only whitespace.
+pub fn validate_not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank").with_message("must not be blank".into()));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Address {
+        #[validate(custom(function = validate_not_blank))]
+        zip: String,
+    }
+
+    #[derive(Debug, Deserialize, Validate)]
+    struct Line {
+        #[validate(range(min = 1, message = "must be positive"))]
+        quantity: i32,
+    }
+
+    #[derive(Debug, Deserialize, Validate)]
+    #[validate(schema(function = validate_period))]
+    struct Payload {
+        #[validate(custom(function = validate_username))]
+        username: String,
+        #[validate(nested)]
+        address: Address,
+        #[validate(nested)]
+        lines: Vec<Line>,
+        starts_on: i32,
+        ends_on: i32,
+    }
+
+    fn validate_period(payload: &Payload) -> Result<(), ValidationError> {
+        if payload.ends_on < payload.starts_on {
+            return Err(
+                ValidationError::new("ends_on").with_message("must not be before starts_on".into())
+            );
+        }
+        Ok(())
+    }
+
+    fn payload() -> Payload {
+        Payload {
+            username: "alice".to_string(),
+            address: Address {
+                zip: "94000".to_string(),
+            },
+            lines: vec![Line { quantity: 1 }, Line { quantity: 2 }],
+            starts_on: 1,
+            ends_on: 2,
+        }
+    }
+
+    #[test]
+    fn accepts_password_mixing_letters_and_digits() {
+        assert!(validate_password("correct1horse").is_ok());
+        assert!(validate_password(&format!("a1{}", "x".repeat(126))).is_ok());
+    }
+
+    #[test]
+    fn rejects_password_outside_length_bounds() {
+        for password in ["abc123", &format!("a1{}", "x".repeat(127))] {
+            let error = validate_password(password).unwrap_err();
+            assert_eq!(error.code, "password_length");
+        }
+    }
+
+    #[test]
+    fn counts_password_length_in_characters() {
+        assert!(validate_password("ééééééé1").is_ok());
+    }
+
+    #[test]
+    fn rejects_password_without_letter_or_digit() {
+        for password in ["onlyletters", "1234567890"] {
+            let error = validate_password(password).unwrap_err();
+            assert_eq!(error.code, "password_strength");
+        }
+    }
+
+    #[test]
+    fn rejects_username_outside_charset() {
+        assert!(validate_username("alice.smith-2_b").is_ok());
+        assert!(validate_username("alice smith").is_err());
+        assert!(validate_username("alice@example").is_err());
+    }
+
+    #[test]
+    fn rejects_blank_value() {
+        assert!(validate_not_blank(" \t\n").is_err());
+        assert!(validate_not_blank(" x ").is_ok());
+    }
+
+    #[test]
+    fn valid_payload_has_no_field_errors() {
+        assert!(payload().validate().is_ok());
+    }
+
+    #[test]
+    fn field_errors_use_paths_and_are_sorted() {
+        let mut payload = payload();
+        payload.username = "alice smith".to_string();
+        payload.address.zip = " ".to_string();
+        payload.lines[1].quantity = 0;
+
+        let errors = field_errors(&payload.validate().unwrap_err());
+        let errors: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|error| (error.field.as_str(), error.message.as_str()))
+            .collect();
+
+        assert_eq!(
+            errors,
+            vec![
+                ("address.zip", "must not be blank"),
+                ("lines[1].quantity", "must be positive"),
+                (
+                    "username",
+                    "may only contain letters, digits, dots, underscores and hyphens"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_level_error_is_reported_under_its_code() {
+        let mut payload = payload();
+        payload.ends_on = 0;
+
+        let errors = field_errors(&payload.validate().unwrap_err());
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "ends_on");
+        assert_eq!(errors[0].message, "must not be before starts_on");
+    }
+
+    #[test]
+    fn field_error_without_message_falls_back_to_code() {
+        let error = ValidationError::new("too_short");
+
+        assert_eq!(error_message(&error), "is invalid (too_short)");
+    }
+}